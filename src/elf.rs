@@ -0,0 +1,830 @@
+//! Loading and writing ELF files as permission-tagged inputs.
+//!
+//! `Image::parse` reads an ELF32 or ELF64 file of either byte order and
+//! lays out its `PT_LOAD` segments as one `Input` starting at the lowest
+//! segment address:
+//!
+//! - Bytes of a segment take its `p_flags` as their `r`, `w` and `x`
+//!   permissions. Where segments overlap, the later one wins.
+//! - Bytes past a segment's `p_filesz` but within its `p_memsz`, such as
+//!   `.bss`, are zero and have the segment's permissions.
+//! - Gaps between segments are zero and have no permissions.
+//! - `nj` is set for every byte that is not executable, and also for
+//!   executable bytes the section and symbol tables mark as data: allocated
+//!   sections without `SHF_EXECINSTR`, `STT_OBJECT` symbols, and the ranges
+//!   following `$d` mapping symbols. Files without section headers only get
+//!   the first rule.
+//!
+//! [`write()`] goes the other way, emitting an executable with one `PT_LOAD`
+//! segment per run of bytes with the same permissions.
+//!
+//! Available only with the `elf` feature enabled. [`write()`] and
+//! [`write_raw`] do not allocate; loading also needs the `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+use bitvec::vec::BitVec;
+use either::Either;
+use embedded_io::Write;
+
+use crate::types::ops::Endian;
+use crate::types::perms::InputRef;
+#[cfg(feature = "alloc")]
+use crate::types::perms::{AddrInputRef, Input, Perms};
+use crate::types::value::Bitness;
+
+const ET_EXEC: u16 = 2;
+const PT_LOAD: u32 = 1;
+const PF_X: u32 = 1;
+const PF_W: u32 = 2;
+const PF_R: u32 = 4;
+#[cfg(feature = "alloc")]
+const SHT_SYMTAB: u32 = 2;
+#[cfg(feature = "alloc")]
+const SHT_NOBITS: u32 = 8;
+#[cfg(feature = "alloc")]
+const SHT_DYNSYM: u32 = 11;
+#[cfg(feature = "alloc")]
+const SHF_ALLOC: u64 = 2;
+#[cfg(feature = "alloc")]
+const SHF_EXECINSTR: u64 = 4;
+#[cfg(feature = "alloc")]
+const STT_OBJECT: u8 = 1;
+const PN_XNUM: u16 = 0xffff;
+
+/// A loaded ELF file.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "elf", feature = "alloc"))]
+/// # {
+/// use portal_pc_asm_common::elf::Image;
+/// use portal_pc_asm_common::types::perms::Perms;
+///
+/// // An ELF64 header followed by one read-execute PT_LOAD segment mapping
+/// // the two bytes at file offset 0x78 to 0x400000, with 6 bytes of memory.
+/// let mut elf = vec![0x7f, b'E', b'L', b'F', 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+/// for (v, n) in [(2u64, 2), (0x3e, 2), (1, 4), (0x400000, 8), (64, 8), (0, 8)] {
+///     elf.extend(&v.to_le_bytes()[..n]); // e_type .. e_shoff
+/// }
+/// for (v, n) in [(0u64, 4), (64, 2), (56, 2), (1, 2), (64, 2), (0, 2), (0, 2)] {
+///     elf.extend(&v.to_le_bytes()[..n]); // e_flags .. e_shstrndx
+/// }
+/// for (v, n) in [(1u64, 4), (5, 4), (0x78, 8), (0x400000, 8), (0x400000, 8), (2, 8), (6, 8), (0x1000, 8)] {
+///     elf.extend(&v.to_le_bytes()[..n]); // p_type .. p_align
+/// }
+/// elf.extend([0x0f, 0x05]); // syscall
+///
+/// let image = Image::parse(&elf, 1 << 20).unwrap();
+/// assert_eq!(image.base, 0x400000);
+/// assert_eq!(image.header.entry, 0x400000);
+/// assert_eq!(image.input.len(), 6);
+///
+/// let bytes: Vec<_> = image.input.as_ref().iter().collect();
+/// let rx = Perms { r: true, w: false, x: true, nj: false };
+/// assert!(bytes[1] == (0x05, rx));
+/// assert!(bytes[5] == (0x00, rx)); // zero-filled past p_filesz
+/// # }
+/// ```
+#[derive(PartialEq, Eq, Clone)]
+#[cfg(feature = "alloc")]
+pub struct Image {
+    /// The properties of the file
+    pub header: Header,
+    /// The address of the first byte of `input`
+    pub base: u64,
+    /// The loaded bytes and their permissions
+    pub input: Input,
+}
+#[cfg(feature = "alloc")]
+impl Image {
+    /// Parses an ELF file and lays out its loadable segments.
+    ///
+    /// The image is allocated in full, from the lowest segment address to the
+    /// end of the highest segment, so a few small segments far apart make a
+    /// large image. Fails with [`Error::TooLarge`] if it would exceed
+    /// `max_size` bytes.
+    ///
+    /// # Examples
+    ///
+    /// A big-endian ELF32 file with a read-execute segment and a read-write
+    /// segment whose last two bytes are `.bss`, four bytes apart:
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "elf", feature = "alloc"))]
+    /// # {
+    /// use portal_pc_asm_common::elf::Image;
+    /// use portal_pc_asm_common::types::perms::Perms;
+    ///
+    /// fn put(elf: &mut Vec<u8>, fields: &[(u64, usize)]) {
+    ///     for &(v, n) in fields {
+    ///         elf.extend(&v.to_be_bytes()[8 - n..]);
+    ///     }
+    /// }
+    /// let mut elf = vec![0x7f, b'E', b'L', b'F', 1, 2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// // e_type .. e_shstrndx
+    /// put(&mut elf, &[(2, 2), (8, 2), (1, 4), (0x10000, 4), (52, 4), (0, 4), (0, 4)]);
+    /// put(&mut elf, &[(52, 2), (32, 2), (2, 2), (40, 2), (0, 2), (0, 2)]);
+    /// // p_type .. p_align of both segments
+    /// put(&mut elf, &[(1, 4), (116, 4), (0x10000, 4), (0x10000, 4), (4, 4), (4, 4), (5, 4), (4, 4)]);
+    /// put(&mut elf, &[(1, 4), (120, 4), (0x10008, 4), (0x10008, 4), (2, 4), (4, 4), (6, 4), (4, 4)]);
+    /// elf.extend([0xc0, 0xc1, 0xc2, 0xc3, 0xd0, 0xd1]);
+    ///
+    /// let image = Image::parse(&elf, 1 << 20).unwrap();
+    /// assert_eq!(image.base, 0x10000);
+    /// let bytes: Vec<_> = image.input.as_ref().iter().collect();
+    /// let code: Vec<u8> = bytes.iter().map(|b| b.0).collect();
+    /// assert_eq!(code, [0xc0, 0xc1, 0xc2, 0xc3, 0, 0, 0, 0, 0xd0, 0xd1, 0, 0]);
+    ///
+    /// let rx = Perms { r: true, w: false, x: true, nj: false };
+    /// let gap = Perms { r: false, w: false, x: false, nj: true };
+    /// let rw = Perms { r: true, w: true, x: false, nj: true };
+    /// assert!(bytes[..4].iter().all(|b| b.1 == rx));
+    /// assert!(bytes[4..8].iter().all(|b| b.1 == gap));
+    /// assert!(bytes[8..].iter().all(|b| b.1 == rw));
+    ///
+    /// assert!(Image::parse(&elf, 11).is_err());
+    /// # }
+    /// ```
+    ///
+    /// A little-endian ELF64 file with one executable segment, whose section
+    /// and symbol tables mark parts of it as data:
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "elf", feature = "alloc"))]
+    /// # {
+    /// use portal_pc_asm_common::elf::Image;
+    ///
+    /// fn put(elf: &mut Vec<u8>, fields: &[(u64, usize)]) {
+    ///     for &(v, n) in fields {
+    ///         elf.extend(&v.to_le_bytes()[..n]);
+    ///     }
+    /// }
+    /// let mut elf = vec![0x7f, b'E', b'L', b'F', 2, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// // e_type .. e_shstrndx, with five section headers at 248
+    /// put(&mut elf, &[(2, 2), (0x3e, 2), (1, 4), (0x1000, 8), (64, 8), (248, 8), (0, 4)]);
+    /// put(&mut elf, &[(64, 2), (56, 2), (1, 2), (64, 2), (5, 2), (0, 2)]);
+    /// // A read-execute PT_LOAD of 16 bytes at 0x1000
+    /// put(&mut elf, &[(1, 4), (5, 4), (120, 8), (0x1000, 8), (0x1000, 8), (16, 8), (16, 8), (1, 8)]);
+    /// elf.extend([0x90; 16]);
+    /// // .symtab at 136: null, `obj` (STT_OBJECT, 2 bytes at 0x1004), `$d` at
+    /// // 0x1008 and `$x` at 0x100a, all in .text
+    /// elf.extend([0; 24]);
+    /// put(&mut elf, &[(1, 4), (1, 1), (0, 1), (1, 2), (0x1004, 8), (2, 8)]);
+    /// put(&mut elf, &[(5, 4), (0, 1), (0, 1), (1, 2), (0x1008, 8), (0, 8)]);
+    /// put(&mut elf, &[(8, 4), (0, 1), (0, 1), (1, 2), (0x100a, 8), (0, 8)]);
+    /// // .strtab at 232
+    /// elf.extend(b"\0obj\0$d\0$x\0\0\0\0\0\0");
+    /// // Section headers: null, .text (alloc, exec), .rodata (alloc),
+    /// // .symtab and .strtab
+    /// elf.extend([0; 64]);
+    /// put(&mut elf, &[(0, 4), (1, 4), (6, 8), (0x1000, 8), (120, 8), (12, 8)]);
+    /// elf.extend([0; 24]);
+    /// put(&mut elf, &[(0, 4), (1, 4), (2, 8), (0x100c, 8), (132, 8), (4, 8)]);
+    /// elf.extend([0; 24]);
+    /// put(&mut elf, &[(0, 4), (2, 4), (0, 8), (0, 8), (136, 8), (96, 8), (4, 4), (0, 4), (0, 8), (24, 8)]);
+    /// put(&mut elf, &[(0, 4), (3, 4), (0, 8), (0, 8), (232, 8), (11, 8)]);
+    /// elf.extend([0; 24]);
+    ///
+    /// let image = Image::parse(&elf, 1 << 20).unwrap();
+    /// let nj: Vec<u8> = image.input.as_ref().iter().map(|(_, p)| p.nj as u8).collect();
+    /// //         code        `obj`       `$d`        .rodata
+    /// assert_eq!(nj, [0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 1, 1]);
+    /// # }
+    /// ```
+    pub fn parse(file: &[u8], max_size: usize) -> Result<Self, Error> {
+        let elf = Reader::new(file)?;
+        let phdrs = elf.program_headers()?;
+        let loads = || phdrs.iter().filter(|p| p.kind == PT_LOAD && p.memsz != 0);
+        let base = loads().map(|p| p.vaddr).min().ok_or(Error::NoSegments)?;
+        let end = loads()
+            .map(|p| p.vaddr.checked_add(p.memsz).ok_or(Error::Malformed))
+            .try_fold(base, |end, e| Ok(end.max(e?)))?;
+        let len = usize::try_from(end - base)
+            .ok()
+            .filter(|&len| len <= max_size)
+            .ok_or(Error::TooLarge)?;
+
+        let mut code = alloc::vec![0u8; len];
+        let mut perms = Perms {
+            r: BitVec::repeat(false, len),
+            w: BitVec::repeat(false, len),
+            x: BitVec::repeat(false, len),
+            nj: BitVec::repeat(false, len),
+        };
+        for p in loads() {
+            if p.filesz > p.memsz {
+                return Err(Error::Malformed);
+            }
+            let start = (p.vaddr - base) as usize;
+            let range = start..start + p.memsz as usize;
+            let data = elf.bytes(p.offset, p.filesz)?;
+            code[range.clone()].fill(0);
+            code[start..start + data.len()].copy_from_slice(data);
+            perms.r[range.clone()].fill(p.flags & PF_R != 0);
+            perms.w[range.clone()].fill(p.flags & PF_W != 0);
+            perms.x[range].fill(p.flags & PF_X != 0);
+        }
+        perms.nj = !perms.x.clone();
+        elf.mark_data(base, &mut perms.nj)?;
+
+        Ok(Image {
+            header: Header {
+                class: elf.class,
+                endian: elf.endian,
+                machine: elf.u16(18)?,
+                flags: elf.u32(if elf.is64() { 48 } else { 36 })?,
+                entry: elf.word(24)?,
+            },
+            base,
+            input: Input::new(code, perms).ok_or(Error::Malformed)?,
+        })
+    }
+
+    /// Returns the loaded bytes placed at their virtual addresses.
+    ///
+    /// Returns `None` only if `base` was changed so that the input would
+    /// extend past `u64::MAX`.
+    pub fn addressed(&self) -> Option<AddrInputRef<'_>> {
+        AddrInputRef::new(self.base, self.input.as_ref())
+    }
+
+    /// Writes the image as an ELF executable; see [`write()`].
+    pub fn write<W: Write>(&self, w: &mut W) -> Result<(), Either<Error, W::Error>> {
+        write(w, &self.header, self.base, self.input.as_ref())
+    }
+}
+/// The ELF header fields describing the file as a whole.
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
+pub struct Header {
+    /// The word size of the file: [`Bitness::I32`] or [`Bitness::I64`]
+    pub class: Bitness,
+    /// The byte order of the file
+    pub endian: Endian,
+    /// The `e_machine` architecture number
+    pub machine: u16,
+    /// The architecture-specific `e_flags`
+    pub flags: u32,
+    /// The entry point address
+    pub entry: u64,
+}
+/// The alignment of the segments [`write()`] emits.
+pub const SEGMENT_ALIGN: u64 = 0x1000;
+
+/// Writes `input`, loaded at `base`, as an ELF executable.
+///
+/// Each run of bytes with the same `r`, `w` and `x` permissions, and at least
+/// one of them, becomes a `PT_LOAD` segment with those flags; bytes without
+/// permissions are left out. Runs are not split where only `nj` changes, so
+/// they are runs of identical `r`, `w` and `x` rather than of identical
+/// [`Perms<bool>`](crate::types::perms::Perms): `nj` has no ELF equivalent
+/// and is not written, and splitting on it would only yield adjacent
+/// segments with the same flags.
+/// Segment file offsets are congruent to their addresses modulo
+/// [`SEGMENT_ALIGN`], as loaders that map whole pages require. Runs sharing a
+/// page still end up with the permissions of one of them when mapped.
+///
+/// Fails with [`Error::Unsupported`] if the class is neither [`Bitness::I32`]
+/// nor [`Bitness::I64`], and with [`Error::TooLarge`] if there are more
+/// segments than an ELF header can count, or an address does not fit the
+/// class.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "elf", feature = "alloc"))]
+/// # {
+/// use bitvec::prelude::*;
+/// use either::Either;
+/// use portal_pc_asm_common::elf::{write, Error, Header, Image};
+/// use portal_pc_asm_common::types::ops::Endian;
+/// use portal_pc_asm_common::types::perms::{InputRef, Perms};
+/// use portal_pc_asm_common::types::value::Bitness;
+///
+/// // A read-execute byte, a byte without permissions, then a read-write byte.
+/// let code = [0xc3, 0, 0];
+/// let perms = Perms {
+///     r: bits![1, 0, 1],
+///     w: bits![0, 0, 1],
+///     x: bits![1, 0, 0],
+///     nj: bits![0, 1, 1],
+/// };
+/// let input = InputRef::new(&code, perms).unwrap();
+/// let header = Header {
+///     class: Bitness::I32,
+///     endian: Endian::Big,
+///     machine: 8,
+///     flags: 0,
+///     entry: 0x10000,
+/// };
+///
+/// let mut elf = Vec::new();
+/// write(&mut elf, &header, 0x10000, input).unwrap();
+///
+/// let image = Image::parse(&elf, 1 << 20).unwrap();
+/// assert_eq!(image.header, header);
+/// assert_eq!(image.base, 0x10000);
+/// assert!(image.input.as_ref() == input);
+///
+/// let header = Header { class: Bitness::I16, ..header };
+/// let result = write(&mut Vec::new(), &header, 0x10000, input);
+/// assert!(matches!(result, Err(Either::Left(Error::Unsupported))));
+/// # }
+/// ```
+pub fn write<W: Write>(
+    w: &mut W,
+    header: &Header,
+    base: u64,
+    input: InputRef<'_>,
+) -> Result<(), Either<Error, W::Error>> {
+    let is64 = match header.class {
+        Bitness::I32 => false,
+        Bitness::I64 => true,
+        _ => return Err(Either::Left(Error::Unsupported)),
+    };
+    let (ehsize, phentsize) = if is64 { (64, 56) } else { (52, 32) };
+    let count = segments(input).count();
+    let phnum = u16::try_from(count)
+        .ok()
+        .filter(|&n| n < PN_XNUM)
+        .ok_or(Either::Left(Error::TooLarge))?;
+    base.checked_add(input.len() as u64)
+        .filter(|&end| is64 || end <= 1 << 32)
+        .ok_or(Either::Left(Error::TooLarge))?;
+    if !is64 && header.entry > u32::MAX as u64 {
+        return Err(Either::Left(Error::TooLarge));
+    }
+    let out = Out {
+        endian: header.endian,
+        is64,
+    };
+
+    let mut buf = [0u8; 64];
+    buf[..4].copy_from_slice(b"\x7fELF");
+    buf[4] = if is64 { 2 } else { 1 };
+    buf[5] = match header.endian {
+        Endian::Little => 1,
+        Endian::Big => 2,
+    };
+    buf[6] = 1;
+    let mut at = out.put(&mut buf, 16, 2, ET_EXEC as u64);
+    at = out.put(&mut buf, at, 2, header.machine as u64);
+    at = out.put(&mut buf, at, 4, 1);
+    at = out.word(&mut buf, at, header.entry);
+    at = out.word(&mut buf, at, ehsize);
+    at = out.word(&mut buf, at, 0);
+    at = out.put(&mut buf, at, 4, header.flags as u64);
+    at = out.put(&mut buf, at, 2, ehsize);
+    at = out.put(&mut buf, at, 2, phentsize);
+    at = out.put(&mut buf, at, 2, phnum as u64);
+    // No section headers.
+    at += 6;
+    w.write_all(&buf[..at]).map_err(Either::Right)?;
+
+    // Lay out the segment contents after the headers, each at a file offset
+    // congruent to its address.
+    let mut offset = ehsize + phentsize * count as u64;
+    let place = |offset: u64, start: usize| {
+        let addr = base + start as u64;
+        offset + (addr.wrapping_sub(offset) % SEGMENT_ALIGN)
+    };
+    for (range, flags) in segments(input) {
+        let addr = base + range.start as u64;
+        let file_offset = place(offset, range.start);
+        let len = range.len() as u64;
+        let mut buf = [0u8; 56];
+        let at = match is64 {
+            true => {
+                let at = out.put(&mut buf, 0, 4, PT_LOAD as u64);
+                let at = out.put(&mut buf, at, 4, flags as u64);
+                let at = out.word(&mut buf, at, file_offset);
+                let at = out.word(&mut buf, at, addr);
+                let at = out.word(&mut buf, at, addr);
+                let at = out.word(&mut buf, at, len);
+                let at = out.word(&mut buf, at, len);
+                out.word(&mut buf, at, SEGMENT_ALIGN)
+            }
+            false => {
+                let at = out.put(&mut buf, 0, 4, PT_LOAD as u64);
+                let at = out.word(&mut buf, at, file_offset);
+                let at = out.word(&mut buf, at, addr);
+                let at = out.word(&mut buf, at, addr);
+                let at = out.word(&mut buf, at, len);
+                let at = out.word(&mut buf, at, len);
+                let at = out.put(&mut buf, at, 4, flags as u64);
+                out.word(&mut buf, at, SEGMENT_ALIGN)
+            }
+        };
+        w.write_all(&buf[..at]).map_err(Either::Right)?;
+        offset = file_offset + len;
+    }
+
+    let mut offset = ehsize + phentsize * count as u64;
+    for (range, _) in segments(input) {
+        let file_offset = place(offset, range.start);
+        let mut padding = (file_offset - offset) as usize;
+        while padding > 0 {
+            let n = padding.min(64);
+            w.write_all(&[0; 64][..n]).map_err(Either::Right)?;
+            padding -= n;
+        }
+        w.write_all(&input.code[range.clone()])
+            .map_err(Either::Right)?;
+        offset = file_offset + range.len() as u64;
+    }
+    Ok(())
+}
+
+/// Writes the bytes of `input` as a raw image, to be loaded at the address
+/// of its first byte.
+pub fn write_raw<W: Write>(w: &mut W, input: InputRef<'_>) -> Result<(), W::Error> {
+    w.write_all(input.code)
+}
+
+/// Returns the runs of bytes of `input` with the same nonempty `r`, `w` and
+/// `x` permissions, along with their `p_flags`.
+fn segments(input: InputRef<'_>) -> impl Iterator<Item = (core::ops::Range<usize>, u32)> + '_ {
+    let flags = move |i: usize| {
+        (input.r[i] as u32 * PF_R) | (input.w[i] as u32 * PF_W) | (input.x[i] as u32 * PF_X)
+    };
+    let mut start = 0;
+    core::iter::from_fn(move || {
+        while start < input.len() && flags(start) == 0 {
+            start += 1;
+        }
+        if start == input.len() {
+            return None;
+        }
+        let f = flags(start);
+        let end = (start..input.len())
+            .find(|&i| flags(i) != f)
+            .unwrap_or(input.len());
+        let range = start..end;
+        start = end;
+        Some((range, f))
+    })
+}
+/// Writes fields of an ELF file in its class and byte order.
+struct Out {
+    endian: Endian,
+    is64: bool,
+}
+impl Out {
+    /// Stores the low `n` bytes of `v` at `at`, returning the offset past
+    /// them.
+    fn put(&self, buf: &mut [u8], at: usize, n: usize, v: u64) -> usize {
+        let bytes = &mut buf[at..at + n];
+        match self.endian {
+            Endian::Big => bytes.copy_from_slice(&v.to_be_bytes()[8 - n..]),
+            Endian::Little => bytes.copy_from_slice(&v.to_le_bytes()[..n]),
+        }
+        at + n
+    }
+
+    /// Stores an address or offset, whose size depends on the class.
+    fn word(&self, buf: &mut [u8], at: usize, v: u64) -> usize {
+        self.put(buf, at, if self.is64 { 8 } else { 4 }, v)
+    }
+}
+/// An error returned when an ELF file cannot be loaded.
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The file does not start with the ELF magic number
+    Magic,
+    /// The file is neither ELF32 nor ELF64, or neither byte order, or a
+    /// header to write has a class other than those
+    Unsupported,
+    /// A header or segment extends past the end of the file
+    Truncated,
+    /// A header holds inconsistent values
+    Malformed,
+    /// The file has no nonempty `PT_LOAD` segment
+    NoSegments,
+    /// The segments span more than the size limit of `Image::parse`, or do
+    /// not fit the file being written
+    TooLarge,
+}
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::Magic => "not an ELF file",
+            Error::Unsupported => "unsupported ELF class or byte order",
+            Error::Truncated => "truncated ELF file",
+            Error::Malformed => "malformed ELF file",
+            Error::NoSegments => "no loadable segments",
+            Error::TooLarge => "segments too large for the ELF file",
+        })
+    }
+}
+impl core::error::Error for Error {}
+
+/// A program header.
+#[cfg(feature = "alloc")]
+struct Phdr {
+    kind: u32,
+    flags: u32,
+    offset: u64,
+    vaddr: u64,
+    filesz: u64,
+    memsz: u64,
+}
+/// A section header.
+#[cfg(feature = "alloc")]
+struct Shdr {
+    kind: u32,
+    flags: u64,
+    addr: u64,
+    offset: u64,
+    size: u64,
+    link: u32,
+    entsize: u64,
+}
+/// A symbol table entry.
+#[cfg(feature = "alloc")]
+struct Sym {
+    name: u32,
+    info: u8,
+    shndx: u16,
+    value: u64,
+    size: u64,
+}
+/// Reads the fields of an ELF file in its class and byte order.
+#[cfg(feature = "alloc")]
+struct Reader<'a> {
+    file: &'a [u8],
+    class: Bitness,
+    endian: Endian,
+}
+#[cfg(feature = "alloc")]
+impl<'a> Reader<'a> {
+    fn new(file: &'a [u8]) -> Result<Self, Error> {
+        let ident = file.get(..16).ok_or(Error::Truncated)?;
+        if ident[..4] != *b"\x7fELF" {
+            return Err(Error::Magic);
+        }
+        let class = match ident[4] {
+            1 => Bitness::I32,
+            2 => Bitness::I64,
+            _ => return Err(Error::Unsupported),
+        };
+        let endian = match ident[5] {
+            1 => Endian::Little,
+            2 => Endian::Big,
+            _ => return Err(Error::Unsupported),
+        };
+        Ok(Reader {
+            file,
+            class,
+            endian,
+        })
+    }
+
+    fn is64(&self) -> bool {
+        self.class == Bitness::I64
+    }
+
+    /// Returns a reader over entry `i` of the table at `offset` with entries
+    /// of `entsize` bytes.
+    fn entry(&self, offset: u64, i: u64, entsize: u64) -> Result<Reader<'a>, Error> {
+        let at = i
+            .checked_mul(entsize)
+            .and_then(|o| offset.checked_add(o))
+            .ok_or(Error::Malformed)?;
+        Ok(Reader {
+            file: self.bytes(at, entsize)?,
+            class: self.class,
+            endian: self.endian,
+        })
+    }
+
+    fn bytes(&self, offset: u64, len: u64) -> Result<&'a [u8], Error> {
+        let start = usize::try_from(offset).map_err(|_| Error::Truncated)?;
+        let len = usize::try_from(len).map_err(|_| Error::Truncated)?;
+        self.file
+            .get(start..start.checked_add(len).ok_or(Error::Truncated)?)
+            .ok_or(Error::Truncated)
+    }
+
+    fn uint<const N: usize>(&self, offset: u64) -> Result<u64, Error> {
+        let bytes = self.bytes(offset, N as u64)?;
+        let fold = |v: u64, &b: &u8| v << 8 | b as u64;
+        Ok(match self.endian {
+            Endian::Big => bytes.iter().fold(0, fold),
+            Endian::Little => bytes.iter().rev().fold(0, fold),
+        })
+    }
+
+    fn u8(&self, offset: u64) -> Result<u8, Error> {
+        Ok(self.uint::<1>(offset)? as u8)
+    }
+
+    fn u16(&self, offset: u64) -> Result<u16, Error> {
+        Ok(self.uint::<2>(offset)? as u16)
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32, Error> {
+        Ok(self.uint::<4>(offset)? as u32)
+    }
+
+    /// Reads an address or offset, whose size depends on the class.
+    fn word(&self, offset: u64) -> Result<u64, Error> {
+        match self.is64() {
+            true => self.uint::<8>(offset),
+            false => self.uint::<4>(offset),
+        }
+    }
+
+    /// Reads the `e_*` header field at the given ELF32 and ELF64 offsets.
+    fn header_u16(&self, off32: u64, off64: u64) -> Result<u16, Error> {
+        self.u16(if self.is64() { off64 } else { off32 })
+    }
+
+    fn section_headers(&self) -> Result<Vec<Shdr>, Error> {
+        let shoff = self.word(if self.is64() { 40 } else { 32 })?;
+        if shoff == 0 {
+            return Ok(Vec::new());
+        }
+        let entsize = self.header_u16(46, 58)? as u64;
+        let mut count = self.header_u16(48, 60)? as u64;
+        let min = if self.is64() { 64 } else { 40 };
+        let read = |i: u64| {
+            let e = self.entry(shoff, i, entsize)?;
+            Ok(match self.is64() {
+                true => Shdr {
+                    kind: e.u32(4)?,
+                    flags: e.word(8)?,
+                    addr: e.word(16)?,
+                    offset: e.word(24)?,
+                    size: e.word(32)?,
+                    link: e.u32(40)?,
+                    entsize: e.word(56)?,
+                },
+                false => Shdr {
+                    kind: e.u32(4)?,
+                    flags: e.word(8)?,
+                    addr: e.word(12)?,
+                    offset: e.word(16)?,
+                    size: e.word(20)?,
+                    link: e.u32(24)?,
+                    entsize: e.word(36)?,
+                },
+            })
+        };
+        if count == 0 {
+            // The real count is in the first section header.
+            self.check_table(shoff, entsize, 1, min)?;
+            count = read(0)?.size;
+        }
+        self.check_table(shoff, entsize, count, min)?;
+        (0..count).map(read).collect()
+    }
+
+    fn program_headers(&self) -> Result<Vec<Phdr>, Error> {
+        let phoff = self.word(if self.is64() { 32 } else { 28 })?;
+        let entsize = self.header_u16(42, 54)? as u64;
+        let mut count = self.header_u16(44, 56)? as u64;
+        if count == PN_XNUM as u64 {
+            // The real count is in `sh_info` of the first section header.
+            let shoff = self.word(if self.is64() { 40 } else { 32 })?;
+            let shentsize = self.header_u16(46, 58)? as u64;
+            self.check_table(shoff, shentsize, 1, if self.is64() { 64 } else { 40 })?;
+            let first = self.entry(shoff, 0, shentsize)?;
+            count = first.u32(if self.is64() { 44 } else { 28 })? as u64;
+        }
+        self.check_table(phoff, entsize, count, if self.is64() { 56 } else { 32 })?;
+        (0..count)
+            .map(|i| {
+                let e = self.entry(phoff, i, entsize)?;
+                Ok(match self.is64() {
+                    true => Phdr {
+                        kind: e.u32(0)?,
+                        flags: e.u32(4)?,
+                        offset: e.word(8)?,
+                        vaddr: e.word(16)?,
+                        filesz: e.word(32)?,
+                        memsz: e.word(40)?,
+                    },
+                    false => Phdr {
+                        kind: e.u32(0)?,
+                        offset: e.word(4)?,
+                        vaddr: e.word(8)?,
+                        filesz: e.word(16)?,
+                        memsz: e.word(20)?,
+                        flags: e.u32(24)?,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Checks that a table of `count` entries of `entsize` bytes, each at
+    /// least `min` bytes long, lies within the file.
+    fn check_table(&self, offset: u64, entsize: u64, count: u64, min: u64) -> Result<(), Error> {
+        if count == 0 {
+            return Ok(());
+        }
+        if entsize < min {
+            return Err(Error::Malformed);
+        }
+        let len = entsize.checked_mul(count).ok_or(Error::Truncated)?;
+        self.bytes(offset, len).map(|_| ())
+    }
+
+    fn symbols(&self, table: &Shdr) -> Result<Vec<Sym>, Error> {
+        let min = if self.is64() { 24 } else { 16 };
+        if table.entsize < min {
+            return Err(Error::Malformed);
+        }
+        self.bytes(table.offset, table.size)?;
+        (0..table.size / table.entsize)
+            .map(|i| {
+                let e = self.entry(table.offset, i, table.entsize)?;
+                Ok(match self.is64() {
+                    true => Sym {
+                        name: e.u32(0)?,
+                        info: e.u8(4)?,
+                        shndx: e.u16(6)?,
+                        value: e.word(8)?,
+                        size: e.word(16)?,
+                    },
+                    false => Sym {
+                        name: e.u32(0)?,
+                        value: e.word(4)?,
+                        size: e.word(8)?,
+                        info: e.u8(12)?,
+                        shndx: e.u16(14)?,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the NUL-terminated string at `offset` in the string table.
+    fn string(&self, table: &Shdr, offset: u32) -> &'a [u8] {
+        let table = self.bytes(table.offset, table.size).unwrap_or(&[]);
+        let s = table.get(offset as usize..).unwrap_or(&[]);
+        &s[..s.iter().position(|&b| b == 0).unwrap_or(s.len())]
+    }
+
+    /// Sets the `nj` bits of executable bytes that the sections and symbols
+    /// mark as data, for an image starting at `base`.
+    fn mark_data(&self, base: u64, nj: &mut BitVec) -> Result<(), Error> {
+        let sections = self.section_headers()?;
+        let mut mark = |start: u64, size: u64| {
+            let len = nj.len() as u64;
+            let start = start.saturating_sub(base).min(len);
+            let end = start.saturating_add(size).min(len);
+            nj[start as usize..end as usize].fill(true);
+        };
+        for s in &sections {
+            if s.flags & SHF_ALLOC != 0 && s.flags & SHF_EXECINSTR == 0 && s.addr >= base {
+                mark(s.addr, s.size);
+            }
+        }
+        let Some(table) = sections
+            .iter()
+            .find(|s| s.kind == SHT_SYMTAB)
+            .or_else(|| sections.iter().find(|s| s.kind == SHT_DYNSYM))
+        else {
+            return Ok(());
+        };
+        let strings = sections.get(table.link as usize);
+        let mut mapping = Vec::new();
+        for sym in self.symbols(table)? {
+            if sym.info & 0xf == STT_OBJECT && sym.value >= base {
+                mark(sym.value, sym.size);
+            }
+            let name = strings.map_or(&[][..], |t| self.string(t, sym.name));
+            let data = match name {
+                [b'$', b'd', rest @ ..] if rest.is_empty() || rest[0] == b'.' => true,
+                [b'$', b'a' | b't' | b'x', rest @ ..] if rest.is_empty() || rest[0] == b'.' => {
+                    false
+                }
+                _ => continue,
+            };
+            mapping.push((sym.shndx, sym.value, data));
+        }
+        // A `$d` symbol marks data up to the next mapping symbol of its
+        // section, or the end of the section.
+        mapping.sort_unstable();
+        for (i, &(shndx, start, data)) in mapping.iter().enumerate() {
+            let Some(section) = sections.get(shndx as usize) else {
+                continue;
+            };
+            if !data || section.kind == SHT_NOBITS || start < base {
+                continue;
+            }
+            let end = match mapping.get(i + 1) {
+                Some(&(next, value, _)) if next == shndx => value,
+                _ => section.addr.saturating_add(section.size),
+            };
+            mark(start, end.saturating_sub(start));
+        }
+        Ok(())
+    }
+}
@@ -15,6 +15,7 @@
 //! - **Memory Operations**: Memory sizing and addressing types
 //! - **Value Types**: Bit-width aware value representations
 //! - **Ratchet**: Cryptographic seed ratcheting (optional, requires `ratchet` feature)
+//! - **ELF Loading**: Loading and writing ELF files as permission-tagged inputs (optional, requires `elf` feature)
 //!
 //! ## Optional Features
 //!
@@ -24,6 +25,7 @@
 //! - `alloc`: Enables allocating types and `Vec` support
 //! - `sha3`: Enables SHA3 hashing support
 //! - `ratchet`: Enables the ratchet module (requires `sha3`)
+//! - `elf`: Enables the ELF module; loading ELF files also requires `alloc`
 //!
 //! ## Examples
 //!
@@ -66,4 +68,6 @@ extern crate alloc;
 pub mod types;
 #[cfg(feature = "ratchet")]
 pub mod ratchet;
+#[cfg(feature = "elf")]
+pub mod elf;
 pub use embedded_io::{Error as IOError, ErrorKind, ErrorType};
@@ -16,8 +16,13 @@
 
 use core::mem::{replace, transmute};
 
+use bitvec::slice::BitSlice;
+use embedded_io::ErrorType;
+use no_error_type::NoError;
 use sha3::Digest;
 
+use crate::types::perms::{InputRef, InputSource, Perms};
+
 /// A cryptographic ratchet based on SHA3-256.
 ///
 /// The ratchet maintains an internal seed and generates a sequence of
@@ -136,6 +141,42 @@ impl Ratchet {
             return Some(&old[..i]);
         })
     }
+    /// Splits an input at positions marked by ratchet-generated values.
+    ///
+    /// Like [`split`](Ratchet::split), but keeps the permissions of each
+    /// chunk. The returned [`SplitInput`] iterates over the chunks, and as an
+    /// [`InputSource`] reads them back to back with the markers removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "ratchet")]
+    /// # {
+    /// use portal_pc_asm_common::ratchet::Ratchet;
+    /// use portal_pc_asm_common::types::perms::{InputRef, Perms};
+    /// use bitvec::prelude::*;
+    ///
+    /// let mut ratchet = Ratchet::from_seed([0u8; 32]);
+    /// let mut data = Vec::new();
+    /// data.extend_from_slice(b"chunk1");
+    /// data.extend_from_slice(&ratchet.next());
+    /// data.extend_from_slice(b"chunk2");
+    ///
+    /// let bits = BitVec::repeat(true, data.len());
+    /// let input = InputRef::new(&data, Perms { r: &*bits, w: &*bits, x: &*bits, nj: &*bits }).unwrap();
+    ///
+    /// let ratchet = Ratchet::from_seed([0u8; 32]);
+    /// let chunks: Vec<&[u8]> = ratchet.split_input(input).map(|c| c.code).collect();
+    /// assert_eq!(chunks, [b"chunk1", b"chunk2"]);
+    /// # }
+    /// ```
+    pub fn split_input<'a>(self, input: InputRef<'a>) -> SplitInput<'a> {
+        SplitInput {
+            ratchet: self,
+            chunk: input.subref(..0),
+            rest: input,
+        }
+    }
     /// Splits a mutable byte slice at positions marked by ratchet-generated values.
     ///
     /// Similar to [`split`](Ratchet::split), but works with mutable slices and
@@ -215,3 +256,85 @@ impl Ratchet {
         })
     }
 }
+/// An input split at ratchet markers, returned by [`Ratchet::split_input`].
+///
+/// Iterating yields each chunk between markers. Reading through
+/// [`InputSource`] yields the same bytes with the markers removed; a single
+/// read never spans two chunks.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(all(feature = "ratchet", feature = "alloc"))]
+/// # {
+/// use portal_pc_asm_common::ratchet::Ratchet;
+/// use portal_pc_asm_common::types::perms::{copy, Input, InputRef, Perms};
+/// use bitvec::prelude::*;
+///
+/// let mut ratchet = Ratchet::from_seed([7u8; 32]);
+/// let mut data = b"ab".to_vec();
+/// data.extend_from_slice(&ratchet.next());
+/// data.extend_from_slice(b"cd");
+///
+/// let bits = BitVec::repeat(true, data.len());
+/// let input = InputRef::new(&data, Perms { r: &*bits, w: &*bits, x: &*bits, nj: &*bits }).unwrap();
+/// let mut source = Ratchet::from_seed([7u8; 32]).split_input(input);
+///
+/// let empty = Perms { r: bitvec![], w: bitvec![], x: bitvec![], nj: bitvec![] };
+/// let mut sink = Input::new(vec![], empty).unwrap();
+/// assert_eq!(copy(&mut source, &mut sink).unwrap(), 4);
+/// assert_eq!(sink.as_ref().code, b"abcd");
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct SplitInput<'a> {
+    ratchet: Ratchet,
+    /// The unread part of the current chunk
+    chunk: InputRef<'a>,
+    /// Everything after the current chunk's marker
+    rest: InputRef<'a>,
+}
+impl<'a> SplitInput<'a> {
+    fn next_chunk(&mut self) -> Option<InputRef<'a>> {
+        let rest = self.rest;
+        if rest.len() == 0 {
+            return None;
+        }
+        let marker = self.ratchet.next();
+        Some(match rest.code.windows(32).position(|w| w == marker) {
+            Some(i) => {
+                self.rest = rest.subref(i + 32..);
+                rest.subref(..i)
+            }
+            None => {
+                self.rest = rest.subref(rest.len()..);
+                rest
+            }
+        })
+    }
+}
+impl<'a> Iterator for SplitInput<'a> {
+    type Item = InputRef<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunk;
+        if chunk.len() > 0 {
+            self.chunk = chunk.subref(chunk.len()..);
+            return Some(chunk);
+        }
+        self.next_chunk()
+    }
+}
+impl ErrorType for SplitInput<'_> {
+    type Error = NoError;
+}
+impl InputSource for SplitInput<'_> {
+    fn read(&mut self, code: &mut [u8], perms: Perms<&mut BitSlice>) -> Result<usize, Self::Error> {
+        while self.chunk.len() == 0 {
+            match self.next_chunk() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        self.chunk.read(code, perms)
+    }
+}
@@ -10,6 +10,9 @@
 //! - [`reg`]: Register abstractions
 //! - [`mem`]: Memory sizing types
 //! - [`value`]: Bit-width aware value types and constants
+//! - [`abi`]: Calling-convention descriptions
+//! - [`code`]: Instruction bit fields and format tables
+//! - [`imm`]: Immediate operand encodability and constant materialization
 //!
 //! ## Note on Deprecations
 //!
@@ -27,6 +30,8 @@ pub mod reg;
 pub mod mem;
 pub mod value;
 pub mod code;
+pub mod imm;
+pub mod abi;
 #[deprecated(
     note = "These reexports will be removed in the next minor release",
     since = "0.1.1"
@@ -0,0 +1,348 @@
+//! Calling-convention descriptions.
+//!
+//! This module describes which registers a calling convention uses for
+//! arguments and return values, which registers a call may clobber, and how
+//! the stack must be laid out at a call. Instrumentation that injects calls
+//! into rewritten code uses these descriptions to know what it must save.
+//!
+//! Register numbers follow the tables in [`super::reg`]; only general-purpose
+//! registers are described. The predefined conventions describe the platform
+//! ABIs as they are, without a context register; [`CallConv::with_ctx`] adds
+//! one.
+
+use super::reg::{aarch64, riscv, x86_64, Reg, RegMap, RegSet};
+
+/// How [`Reg::CTX`] is made available to code following a convention.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::abi::{CtxRole, SYSV_X86_64};
+/// use portal_pc_asm_common::types::reg::x86_64;
+///
+/// let pinned = SYSV_X86_64.with_ctx(CtxRole::Pinned(x86_64::R15));
+/// assert_eq!(pinned.ctx_reg(), Some(x86_64::R15));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum CtxRole {
+    /// The context is not available
+    None,
+    /// The context is passed as a hidden first argument, shifting every other
+    /// argument by one register
+    LeadingArg,
+    /// The context lives in a register that is reserved for it, which code
+    /// must neither allocate nor clobber
+    Pinned(Reg),
+}
+/// A calling convention.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::abi::WIN64;
+/// use portal_pc_asm_common::types::reg::x86_64;
+///
+/// // Registers an injected call may clobber
+/// assert!(WIN64.caller_saved.contains(x86_64::R10));
+/// assert!(!WIN64.caller_saved.contains(x86_64::RSI));
+/// assert_eq!(WIN64.shadow_space, 32);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct CallConv {
+    /// A short name for the convention
+    pub name: &'static str,
+    /// The register names of the architecture
+    pub regs: &'static RegMap,
+    /// Registers holding integer arguments, in order
+    pub args: &'static [Reg],
+    /// Registers holding integer return values, in order
+    pub rets: &'static [Reg],
+    /// Registers a callee must preserve
+    pub callee_saved: RegSet,
+    /// Registers a call may clobber
+    pub caller_saved: RegSet,
+    /// The alignment of the stack pointer at a call, in bytes
+    pub stack_align: u32,
+    /// The number of bytes below the stack pointer that signal handlers and
+    /// interrupts leave untouched
+    pub red_zone: u32,
+    /// The number of bytes the caller must reserve above the return address
+    /// for the callee to spill register arguments
+    pub shadow_space: u32,
+    /// How [`Reg::CTX`] is made available
+    pub ctx: CtxRole,
+}
+impl CallConv {
+    /// Returns this convention with a different [`CtxRole`].
+    ///
+    /// A pinned register is removed from the caller- and callee-saved sets,
+    /// since code following the convention must not touch it. Argument and
+    /// return registers are positional, so if the pinned register is one of
+    /// them, the list stops just before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::abi::{CtxRole, SYSV_X86_64};
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let pinned = SYSV_X86_64.with_ctx(CtxRole::Pinned(x86_64::RCX));
+    /// assert_eq!(pinned.args, &[x86_64::RDI, x86_64::RSI, x86_64::RDX]);
+    /// assert!(!pinned.caller_saved.contains(x86_64::RCX));
+    /// ```
+    pub const fn with_ctx(mut self, ctx: CtxRole) -> Self {
+        if let CtxRole::Pinned(reg) = ctx {
+            self.caller_saved = self.caller_saved.without(reg);
+            self.callee_saved = self.callee_saved.without(reg);
+            self.args = before(self.args, reg);
+            self.rets = before(self.rets, reg);
+        }
+        self.ctx = ctx;
+        self
+    }
+
+    /// Returns the architectural register holding [`Reg::CTX`] on entry to a
+    /// function, if any.
+    pub const fn ctx_reg(&self) -> Option<Reg> {
+        match self.ctx {
+            CtxRole::None => None,
+            CtxRole::LeadingArg => match self.args {
+                [first, ..] => Some(*first),
+                [] => None,
+            },
+            CtxRole::Pinned(reg) => Some(reg),
+        }
+    }
+
+    /// Returns the register holding the `n`th user argument, accounting for
+    /// a context passed as a leading argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::abi::{CtxRole, SYSV_X86_64};
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// assert_eq!(SYSV_X86_64.ctx_reg(), None);
+    /// assert_eq!(SYSV_X86_64.arg(0), Some(x86_64::RDI));
+    /// assert_eq!(SYSV_X86_64.arg(6), None);
+    ///
+    /// let leading = SYSV_X86_64.with_ctx(CtxRole::LeadingArg);
+    /// assert_eq!(leading.ctx_reg(), Some(x86_64::RDI));
+    /// assert_eq!(leading.arg(0), Some(x86_64::RSI));
+    /// assert_eq!(leading.arg(5), None);
+    /// ```
+    pub fn arg(&self, n: usize) -> Option<Reg> {
+        let skip = match self.ctx {
+            CtxRole::LeadingArg => 1,
+            _ => 0,
+        };
+        self.args.get(n + skip).copied()
+    }
+
+    /// Returns `true` if a call may change `reg`.
+    pub const fn clobbers(&self, reg: Reg) -> bool {
+        self.caller_saved.contains(reg)
+    }
+}
+/// Returns the registers of `regs` before `reg`, or all of them if `reg` is
+/// absent.
+const fn before(regs: &'static [Reg], reg: Reg) -> &'static [Reg] {
+    let mut i = 0;
+    while i < regs.len() {
+        if regs[i].0 == reg.0 {
+            return regs.split_at(i).0;
+        }
+        i += 1;
+    }
+    regs
+}
+
+/// The System V AMD64 convention used by Linux, the BSDs and macOS.
+pub const SYSV_X86_64: CallConv = CallConv {
+    name: "sysv-x86_64",
+    regs: &x86_64::REGS,
+    args: &[
+        x86_64::RDI,
+        x86_64::RSI,
+        x86_64::RDX,
+        x86_64::RCX,
+        x86_64::R8,
+        x86_64::R9,
+    ],
+    rets: &[x86_64::RAX, x86_64::RDX],
+    callee_saved: RegSet::from_regs(&[
+        x86_64::RBX,
+        x86_64::RSP,
+        x86_64::RBP,
+        x86_64::R12,
+        x86_64::R13,
+        x86_64::R14,
+        x86_64::R15,
+    ]),
+    caller_saved: RegSet::from_regs(&[
+        x86_64::RAX,
+        x86_64::RCX,
+        x86_64::RDX,
+        x86_64::RSI,
+        x86_64::RDI,
+        x86_64::R8,
+        x86_64::R9,
+        x86_64::R10,
+        x86_64::R11,
+    ]),
+    stack_align: 16,
+    red_zone: 128,
+    shadow_space: 0,
+    ctx: CtxRole::None,
+};
+
+/// The Microsoft x64 convention used by Windows.
+pub const WIN64: CallConv = CallConv {
+    name: "win64",
+    regs: &x86_64::REGS,
+    args: &[x86_64::RCX, x86_64::RDX, x86_64::R8, x86_64::R9],
+    rets: &[x86_64::RAX],
+    callee_saved: RegSet::from_regs(&[
+        x86_64::RBX,
+        x86_64::RSP,
+        x86_64::RBP,
+        x86_64::RSI,
+        x86_64::RDI,
+        x86_64::R12,
+        x86_64::R13,
+        x86_64::R14,
+        x86_64::R15,
+    ]),
+    caller_saved: RegSet::from_regs(&[
+        x86_64::RAX,
+        x86_64::RCX,
+        x86_64::RDX,
+        x86_64::R8,
+        x86_64::R9,
+        x86_64::R10,
+        x86_64::R11,
+    ]),
+    stack_align: 16,
+    red_zone: 0,
+    shadow_space: 32,
+    ctx: CtxRole::None,
+};
+
+/// The Arm 64-bit procedure call standard, as used on Linux.
+///
+/// The platform register `x18` is in neither saved set, since some platforms
+/// reserve it. The link register `x30` is caller-saved because the call itself
+/// overwrites it.
+pub const AAPCS64: CallConv = CallConv {
+    name: "aapcs64",
+    regs: &aarch64::REGS,
+    args: &[
+        aarch64::x(0),
+        aarch64::x(1),
+        aarch64::x(2),
+        aarch64::x(3),
+        aarch64::x(4),
+        aarch64::x(5),
+        aarch64::x(6),
+        aarch64::x(7),
+    ],
+    rets: &[aarch64::x(0), aarch64::x(1)],
+    callee_saved: RegSet::from_regs(&[
+        aarch64::x(19),
+        aarch64::x(20),
+        aarch64::x(21),
+        aarch64::x(22),
+        aarch64::x(23),
+        aarch64::x(24),
+        aarch64::x(25),
+        aarch64::x(26),
+        aarch64::x(27),
+        aarch64::x(28),
+        aarch64::FP,
+        aarch64::SP,
+    ]),
+    caller_saved: RegSet::from_regs(&[
+        aarch64::x(0),
+        aarch64::x(1),
+        aarch64::x(2),
+        aarch64::x(3),
+        aarch64::x(4),
+        aarch64::x(5),
+        aarch64::x(6),
+        aarch64::x(7),
+        aarch64::x(8),
+        aarch64::x(9),
+        aarch64::x(10),
+        aarch64::x(11),
+        aarch64::x(12),
+        aarch64::x(13),
+        aarch64::x(14),
+        aarch64::x(15),
+        aarch64::x(16),
+        aarch64::x(17),
+        aarch64::LR,
+    ]),
+    stack_align: 16,
+    red_zone: 0,
+    shadow_space: 0,
+    ctx: CtxRole::None,
+};
+
+/// The RISC-V LP64 integer calling convention.
+///
+/// `gp` and `tp` are in neither saved set, since code must not modify them.
+pub const RISCV_LP64: CallConv = CallConv {
+    name: "riscv-lp64",
+    regs: &riscv::RV64,
+    args: &[
+        riscv::A0,
+        riscv::A1,
+        riscv::A2,
+        riscv::A3,
+        riscv::A4,
+        riscv::A5,
+        riscv::A6,
+        riscv::A7,
+    ],
+    rets: &[riscv::A0, riscv::A1],
+    callee_saved: RegSet::from_regs(&[
+        riscv::SP,
+        riscv::S0,
+        riscv::S1,
+        riscv::S2,
+        riscv::S3,
+        riscv::S4,
+        riscv::S5,
+        riscv::S6,
+        riscv::S7,
+        riscv::S8,
+        riscv::S9,
+        riscv::S10,
+        riscv::S11,
+    ]),
+    caller_saved: RegSet::from_regs(&[
+        riscv::RA,
+        riscv::T0,
+        riscv::T1,
+        riscv::T2,
+        riscv::A0,
+        riscv::A1,
+        riscv::A2,
+        riscv::A3,
+        riscv::A4,
+        riscv::A5,
+        riscv::A6,
+        riscv::A7,
+        riscv::T3,
+        riscv::T4,
+        riscv::T5,
+        riscv::T6,
+    ]),
+    stack_align: 16,
+    red_zone: 0,
+    shadow_space: 0,
+    ctx: CtxRole::None,
+};
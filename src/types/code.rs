@@ -1,6 +1,12 @@
+use core::fmt::{self, Display, Formatter};
+use core::marker::PhantomData;
 use core::ops::Range;
 
+use super::ops::{Endian, Sign};
+use super::value::Constant;
 use super::*;
+
+pub mod riscv;
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 #[repr(transparent)]
 pub struct InstCodeI4(pub u32);
@@ -30,42 +36,487 @@ impl InstCodeI4 {
         val.into()
     }
 }
+/// A 16-bit instruction, such as a RISC-V compressed or Thumb instruction.
+///
+/// The counterpart of [`InstCodeI4`] with the same field API.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::InstCodeI2;
+///
+/// // c.addi a0, 1
+/// let code = InstCodeI2(0x0001).with([7..12].into_iter(), 10u16).with([2..7].into_iter(), 1u16);
+/// assert_eq!(code, InstCodeI2(0x0505));
+/// assert_eq!(code.extract::<u16>([7..12].into_iter()), 10);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+#[repr(transparent)]
+pub struct InstCodeI2(pub u16);
+impl InstCodeI2 {
+    pub fn with(self, inst: impl Iterator<Item = Range<u32>>, val: impl Into<u16>) -> Self {
+        let val = val.into();
+        let mut code = self.0;
+        let mut len = 0;
+        for r in inst {
+            let mask = (((1u32 << (r.end - r.start)) - 1) << r.start) as u16;
+            code = (code & !mask) | ((((val as u32) >> len) << r.start) as u16 & mask);
+            len += r.end - r.start;
+        }
+        InstCodeI2(code)
+    }
+    pub fn extract<T>(&self, list: impl Iterator<Item = Range<u32>>) -> T
+    where
+        u16: Into<T>,
+    {
+        let mut val = 0u16;
+        let mut len = 0;
+        for r in list {
+            let mask = (((1u32 << (r.end - r.start)) - 1) << r.start) as u16;
+            val |= ((self.0 & mask) >> r.start) << len;
+            len += r.end - r.start;
+        }
+        val.into()
+    }
+}
+/// A fixed-width instruction word that [`Field`]s and [`Format`]s address.
+///
+/// Implemented by [`InstCodeI4`] and [`InstCodeI2`]. Variable-length
+/// instructions in an [`InstCodeSlice`] are accessed through its own
+/// [`InstCodeSlice::extract`] and [`InstCodeSlice::with`] instead.
+pub trait InstCode: Copy {
+    /// Returns the instruction bits, zero-extended.
+    fn bits(self) -> u32;
+    /// Creates an instruction from the low bits of `bits`.
+    fn from_bits(bits: u32) -> Self;
+}
+impl InstCode for InstCodeI4 {
+    fn bits(self) -> u32 {
+        self.0
+    }
+    fn from_bits(bits: u32) -> Self {
+        InstCodeI4(bits)
+    }
+}
+impl InstCode for InstCodeI2 {
+    fn bits(self) -> u32 {
+        self.0 as u32
+    }
+    fn from_bits(bits: u32) -> Self {
+        InstCodeI2(bits as u16)
+    }
+}
+/// A named instruction field made of one or more bit ranges of an
+/// [`InstCode`].
+///
+/// The ranges are listed from the least significant bits of the field value
+/// upward. The value stored in the field may be signed, may be scaled by an
+/// implicit left shift, whose low bits are not encoded, and may be offset by a
+/// constant bias, as for the RISC-V compressed registers `x8` to `x15`.
+///
+/// # Examples
+///
+/// The RISC-V B-type immediate `imm[12|10:5|4:1|11]`, whose bit 0 is always
+/// zero:
+///
+/// ```
+/// use portal_pc_asm_common::types::code::{Field, FieldError, InstCodeI4};
+///
+/// const B_IMM: Field = Field::new("imm", &[8..12, 25..31, 7..8, 31..32])
+///     .signed()
+///     .scaled(1);
+///
+/// // beq x0, x0, -4
+/// let beq = InstCodeI4(0xfe000ee3);
+/// assert_eq!(B_IMM.get(beq), -4);
+/// assert_eq!(B_IMM.set(InstCodeI4(0x00000063), -4), Ok(beq));
+///
+/// assert_eq!((B_IMM.min(), B_IMM.max()), (-4096, 4094));
+/// assert_eq!(B_IMM.encode(4096), Err(FieldError::OutOfRange));
+/// assert_eq!(B_IMM.encode(3), Err(FieldError::Misaligned));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Field {
+    /// The name of the field, as used in the architecture manual
+    pub name: &'static str,
+    /// The bit ranges of the instruction holding the field, least significant
+    /// first
+    pub ranges: &'static [Range<u32>],
+    /// Whether the field value is sign-extended
+    pub sign: Sign,
+    /// The number of implicit low zero bits of the field value
+    pub shift: u32,
+    /// The constant added to the encoded value
+    pub bias: i64,
+}
+impl Field {
+    /// Creates an unsigned, unscaled field over `ranges`.
+    pub const fn new(name: &'static str, ranges: &'static [Range<u32>]) -> Self {
+        Field {
+            name,
+            ranges,
+            sign: Sign::Unsigned,
+            shift: 0,
+            bias: 0,
+        }
+    }
+
+    /// Returns this field with a sign-extended value.
+    pub const fn signed(self) -> Self {
+        Field {
+            sign: Sign::Signed,
+            ..self
+        }
+    }
+
+    /// Returns this field with its value scaled by `1 << shift`.
+    pub const fn scaled(self, shift: u32) -> Self {
+        Field { shift, ..self }
+    }
+
+    /// Returns this field with `bias` added to its value after scaling.
+    pub const fn biased(self, bias: i64) -> Self {
+        Field { bias, ..self }
+    }
+
+    /// Returns the number of instruction bits the field occupies.
+    pub const fn width(&self) -> u32 {
+        let mut width = 0;
+        let mut i = 0;
+        while i < self.ranges.len() {
+            width += self.ranges[i].end - self.ranges[i].start;
+            i += 1;
+        }
+        width
+    }
+
+    /// Returns the smallest value the field can hold.
+    pub const fn min(&self) -> i64 {
+        let min = match self.sign {
+            Sign::Signed => -(1i64 << (self.width() - 1)) << self.shift,
+            _ => 0,
+        };
+        min + self.bias
+    }
+
+    /// Returns the largest value the field can hold.
+    pub const fn max(&self) -> i64 {
+        let bits = match self.sign {
+            Sign::Signed => self.width() - 1,
+            _ => self.width(),
+        };
+        (((1i64 << bits) - 1) << self.shift) + self.bias
+    }
+
+    /// Returns the instruction bits encoding `value`, packed into the low
+    /// [`Field::width`] bits, as accepted by [`InstCodeI4::with`].
+    pub fn encode(&self, value: i64) -> Result<u32, FieldError> {
+        if value < self.min() || value > self.max() {
+            return Err(FieldError::OutOfRange);
+        }
+        let value = value - self.bias;
+        if value & ((1i64 << self.shift) - 1) != 0 {
+            return Err(FieldError::Misaligned);
+        }
+        let mask = (1u64 << self.width()) - 1;
+        Ok(((value >> self.shift) as u64 & mask) as u32)
+    }
+
+    /// Returns the value encoded by the packed field bits `raw`.
+    pub fn decode(&self, raw: u32) -> i64 {
+        let width = self.width();
+        let raw = raw as i64 & ((1i64 << width) - 1);
+        let value = match self.sign {
+            Sign::Signed => (raw << (64 - width)) >> (64 - width),
+            _ => raw,
+        };
+        (value << self.shift) + self.bias
+    }
+
+    /// Returns `true` if `value` can be stored in the field.
+    pub fn fits(&self, value: i64) -> bool {
+        self.encode(value).is_ok()
+    }
+
+    /// Reads the field from `code`.
+    pub fn get<C: InstCode>(&self, code: C) -> i64 {
+        self.decode(InstCodeI4(code.bits()).extract(self.ranges.iter().cloned()))
+    }
+
+    /// Returns `code` with the field set to `value`.
+    pub fn set<C: InstCode>(&self, code: C, value: impl Into<i64>) -> Result<C, FieldError> {
+        let raw = self.encode(value.into())?;
+        let code = InstCodeI4(code.bits()).with(self.ranges.iter().cloned(), raw);
+        Ok(C::from_bits(code.0))
+    }
+}
+/// An error returned when a value cannot be stored in a [`Field`].
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[non_exhaustive]
+pub enum FieldError {
+    /// The value is outside the range of the field
+    OutOfRange,
+    /// The value has nonzero bits below the scaling of the field
+    Misaligned,
+}
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FieldError::OutOfRange => "value out of range for field",
+            FieldError::Misaligned => "value not a multiple of the field scale",
+        })
+    }
+}
+impl core::error::Error for FieldError {}
+/// The encoding of one fixed-width instruction: the bits fixed by its opcode
+/// and the fields holding its operands.
+///
+/// `C` is the instruction word type, [`InstCodeI4`] unless stated otherwise;
+/// tables of 16-bit instructions use [`InstCodeI2`].
+///
+/// An instruction matches the format when its bits under `mask` equal
+/// `pattern`. Tables of formats are searched with [`decode`].
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::{Format, InstCodeI4};
+/// use portal_pc_asm_common::types::code::riscv::{I_IMM, RD, RS1};
+///
+/// const ADDI: Format = Format::new("addi", 0x00000013, 0x0000707f, &[RD, RS1, I_IMM]);
+///
+/// // addi a0, a1, -1
+/// let code = ADDI.encode(&[10, 11, -1]).unwrap();
+/// assert_eq!(code, InstCodeI4(0xfff58513));
+/// assert!(ADDI.matches(code));
+/// assert_eq!(ADDI.field("imm").unwrap().get(code), -1);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Format<C = InstCodeI4> {
+    /// The mnemonic of the instruction
+    pub name: &'static str,
+    /// The values of the fixed bits
+    pub pattern: u32,
+    /// The bits fixed by the format
+    pub mask: u32,
+    /// The operand fields, in assembly operand order
+    pub fields: &'static [Field],
+    /// The instruction word type
+    pub code: PhantomData<C>,
+}
+impl<C: InstCode> Format<C> {
+    /// Creates a format.
+    pub const fn new(
+        name: &'static str,
+        pattern: u32,
+        mask: u32,
+        fields: &'static [Field],
+    ) -> Self {
+        Format {
+            name,
+            pattern,
+            mask,
+            fields,
+            code: PhantomData,
+        }
+    }
+
+    /// Returns `true` if `code` is an instance of this format.
+    pub fn matches(&self, code: C) -> bool {
+        code.bits() & self.mask == self.pattern
+    }
+
+    /// Returns the number of fixed bits; a format with more fixed bits is a
+    /// more specific encoding.
+    pub const fn specificity(&self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    /// Returns the field called `name`.
+    pub fn field(&self, name: &str) -> Option<&'static Field> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Encodes the instruction with the operand `values`, given in the order
+    /// of [`Format::fields`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of values differs from the number of fields.
+    pub fn encode(&self, values: &[i64]) -> Result<C, FieldError> {
+        assert_eq!(values.len(), self.fields.len(), "wrong number of operands");
+        self.fields
+            .iter()
+            .zip(values)
+            .try_fold(C::from_bits(self.pattern), |code, (f, &v)| f.set(code, v))
+    }
+
+    /// Decodes `code` if it is an instance of this format.
+    pub fn decode(&'static self, code: C) -> Option<Decoded<C>> {
+        self.matches(code).then_some(Decoded { format: self, code })
+    }
+}
+/// Finds the most specific format in `table` matching `code`.
+///
+/// When several formats match, the one with the most fixed bits wins, so a
+/// table may list special cases such as `fence.tso` next to the general
+/// encoding. Among equally specific formats the first is chosen.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::{decode, InstCodeI4};
+/// use portal_pc_asm_common::types::code::riscv::RV64G;
+///
+/// let inst = decode(RV64G, InstCodeI4(0xfe000ee3)).unwrap();
+/// assert_eq!(inst.format.name, "beq");
+/// assert_eq!(inst.get("imm"), Some(-4));
+///
+/// assert_eq!(decode(RV64G, InstCodeI4(0x8330000f)).unwrap().format.name, "fence.tso");
+/// assert_eq!(decode(RV64G, InstCodeI4(0x0ff0000f)).unwrap().format.name, "fence");
+/// assert!(decode(RV64G, InstCodeI4(0)).is_none());
+/// ```
+pub fn decode<C: InstCode>(table: &'static [Format<C>], code: C) -> Option<Decoded<C>> {
+    table
+        .iter()
+        .filter(|f| f.matches(code))
+        .reduce(|best, f| {
+            if f.specificity() > best.specificity() {
+                f
+            } else {
+                best
+            }
+        })
+        .map(|format| Decoded { format, code })
+}
+/// An instruction together with the format it was decoded as.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Decoded<C: 'static = InstCodeI4> {
+    /// The matching format
+    pub format: &'static Format<C>,
+    /// The instruction
+    pub code: C,
+}
+impl<C: InstCode> Decoded<C> {
+    /// Returns the value of the field called `name`.
+    pub fn get(&self, name: &str) -> Option<i64> {
+        Some(self.format.field(name)?.get(self.code))
+    }
+
+    /// Returns the name and value of every operand field, in operand order.
+    pub fn operands(&self) -> impl Iterator<Item = (&'static str, i64)> + '_ {
+        self.format
+            .fields
+            .iter()
+            .map(|f| (f.name, f.get(self.code)))
+    }
+}
+/// A variable-length instruction stored as bytes, such as an x86 instruction.
+///
+/// Bits are numbered as if the bytes were a single integer in the given byte
+/// order: bit 0 is the least significant bit of the first byte for
+/// [`Endian::Little`] and of the last byte for [`Endian::Big`]. Fields are
+/// lists of bit ranges, least significant first, as with [`InstCodeI4`], and
+/// may be up to 512 bits wide.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::InstCodeSlice;
+/// use portal_pc_asm_common::types::ops::Endian;
+/// use portal_pc_asm_common::types::value::Constant;
+///
+/// // mov rax, 0x1122334455667788
+/// let mut mov = InstCodeSlice([0x48, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0]);
+/// mov = mov.with(Endian::Little, [16..80].into_iter(), 0x1122334455667788u64);
+/// assert_eq!(mov.0[2..], 0x1122334455667788u64.to_le_bytes());
+///
+/// let imm = mov.extract(Endian::Little, [16..80].into_iter());
+/// assert_eq!(imm.to_u64(), Some(0x1122334455667788));
+/// assert_eq!(mov.extract(Endian::Big, [72..80].into_iter()), Constant::from(0x48u8));
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 #[repr(transparent)]
 pub struct InstCodeSlice<S>(pub S);
 impl<S: AsRef<[u8]>> InstCodeSlice<S> {
-    pub fn extract<T: From<u64>>(&self, list: impl Iterator<Item = Range<usize>>) -> T {
+    /// Returns the number of bits in the instruction.
+    pub fn bit_len(&self) -> usize {
+        self.0.as_ref().len() * 8
+    }
+
+    /// Returns bit `i` of the instruction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than [`InstCodeSlice::bit_len`].
+    pub fn bit(&self, endian: Endian, i: usize) -> bool {
         let bytes = self.0.as_ref();
-        let mut val = 0u64;
+        (bytes[byte_index(bytes.len(), endian, i)] >> (i % 8)) & 1 != 0
+    }
+
+    /// Reads the bits in `list` into a constant, the first range providing
+    /// the least significant bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range extends past the end of the instruction or the
+    /// ranges total more than 512 bits.
+    pub fn extract(&self, endian: Endian, list: impl Iterator<Item = Range<usize>>) -> Constant {
+        let mut val = Constant::ZERO;
         let mut len = 0;
         for r in list {
-            let mut part = 0u64;
-            for i in r.clone() {
-                part |= (bytes[i >> 3] as u64) << (i << 3);
+            for i in r {
+                assert!(len < 512, "field wider than 512 bits");
+                if self.bit(endian, i) {
+                    val.data[len / 64] |= 1 << (len % 64);
+                }
+                len += 1;
             }
-            val |= part << len;
-            len += (r.end - r.start) * 8;
         }
-        val.into()
+        val
     }
 }
 impl<S: AsMut<[u8]>> InstCodeSlice<S> {
-    pub fn with(mut self, inst: impl Iterator<Item = Range<usize>>, val: impl Into<u64>) -> Self {
-        let val = val.into();
+    /// Sets bit `i` of the instruction to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is past the end of the instruction.
+    pub fn set_bit(&mut self, endian: Endian, i: usize, value: bool) {
         let bytes = self.0.as_mut();
+        let byte = &mut bytes[byte_index(bytes.len(), endian, i)];
+        *byte = (*byte & !(1 << (i % 8))) | ((value as u8) << (i % 8));
+    }
+
+    /// Returns the instruction with the bits in `list` set from the low bits
+    /// of `val`, the first range receiving the least significant bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a range extends past the end of the instruction or the
+    /// ranges total more than 512 bits.
+    pub fn with(
+        mut self,
+        endian: Endian,
+        list: impl Iterator<Item = Range<usize>>,
+        val: impl Into<Constant>,
+    ) -> Self {
+        let val = val.into();
         let mut len = 0;
-        for r in inst {
-            let mut part = 0u64;
-            for i in r.clone() {
-                part |= (bytes[i >> 3] as u64) << (i << 3);
-            }
-            let mask = ((1u64 << ((r.end - r.start) * 8)) - 1) << (r.start * 8);
-            part = (part & !mask) | (((val >> len) << (r.start * 8)) & mask);
-            for i in r.clone() {
-                bytes[i >> 3] = ((part >> (i << 3)) & 0xff) as u8;
+        for r in list {
+            for i in r {
+                assert!(len < 512, "field wider than 512 bits");
+                self.set_bit(endian, i, val.bit(len as u32));
+                len += 1;
             }
-            len += (r.end - r.start) * 8;
         }
         self
     }
 }
+/// Returns the index of the byte holding bit `i` of a `len`-byte instruction.
+fn byte_index(len: usize, endian: Endian, i: usize) -> usize {
+    match endian {
+        Endian::Big => len.checked_sub(i / 8 + 1).expect("bit index out of range"),
+        _ => i / 8,
+    }
+}
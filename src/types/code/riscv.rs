@@ -0,0 +1,394 @@
+//! Instruction formats of the RISC-V RV64GC ISA.
+//!
+//! [`RV64G`] covers RV64I with the M, A, F and D extensions, Zicsr and
+//! Zifencei. [`RV64C`] covers the 16-bit instructions of the C extension for
+//! RV64 with D, as [`InstCodeI2`] formats; [`inst_len`] tells the two apart in
+//! a code stream. Register fields hold register numbers, which are the
+//! [`Reg`] numbers of [`reg::riscv`] for integer registers. The compressed
+//! register fields `rd'`, `rs1'` and `rs2'` decode to the full numbers 8 to
+//! 15. Implicit operands, such as `sp` of `c.lwsp`, are not fields.
+//!
+//! The tables do not check fields the specification reserves, such as `rd`
+//! of `fence`, the unassigned rounding modes or the zero immediate of
+//! `c.addi4spn`; the all-zero illegal instruction therefore decodes as
+//! `c.addi4spn`. Hints decode as the instruction they are encoded as, such as
+//! `c.addi` with `rd` zero.
+//!
+//! [`Reg`]: crate::types::reg::Reg
+//! [`reg::riscv`]: crate::types::reg::riscv
+
+// Single-range fields are written as one-element range slices on purpose.
+#![allow(clippy::single_range_in_vec_init)]
+
+use super::*;
+use crate::types::perms::InputRef;
+
+/// The destination register.
+pub const RD: Field = Field::new("rd", &[7..12]);
+/// The first source register.
+pub const RS1: Field = Field::new("rs1", &[15..20]);
+/// The second source register.
+pub const RS2: Field = Field::new("rs2", &[20..25]);
+/// The third source register of fused multiply-add instructions.
+pub const RS3: Field = Field::new("rs3", &[27..32]);
+/// The floating-point rounding mode.
+pub const RM: Field = Field::new("rm", &[12..15]);
+/// The I-type immediate `imm[11:0]`.
+pub const I_IMM: Field = Field::new("imm", &[20..32]).signed();
+/// The S-type immediate `imm[11:5|4:0]`.
+pub const S_IMM: Field = Field::new("imm", &[7..12, 25..32]).signed();
+/// The B-type immediate `imm[12|10:5|4:1|11]`.
+pub const B_IMM: Field = Field::new("imm", &[8..12, 25..31, 7..8, 31..32])
+    .signed()
+    .scaled(1);
+/// The U-type immediate `imm[31:12]`, as the sign-extended value it adds.
+pub const U_IMM: Field = Field::new("imm", &[12..32]).signed().scaled(12);
+/// The J-type immediate `imm[20|10:1|11|19:12]`.
+pub const J_IMM: Field = Field::new("imm", &[21..31, 20..21, 12..20, 31..32])
+    .signed()
+    .scaled(1);
+/// The shift amount of 64-bit immediate shifts.
+pub const SHAMT: Field = Field::new("shamt", &[20..26]);
+/// The shift amount of 32-bit immediate shifts.
+pub const SHAMTW: Field = Field::new("shamt", &[20..25]);
+/// The CSR number.
+pub const CSR: Field = Field::new("csr", &[20..32]);
+/// The immediate source of CSR instructions.
+pub const ZIMM: Field = Field::new("zimm", &[15..20]);
+/// The acquire bit of atomic instructions.
+pub const AQ: Field = Field::new("aq", &[26..27]);
+/// The release bit of atomic instructions.
+pub const RL: Field = Field::new("rl", &[25..26]);
+/// The successor set of `fence`.
+pub const SUCC: Field = Field::new("succ", &[20..24]);
+/// The predecessor set of `fence`.
+pub const PRED: Field = Field::new("pred", &[24..28]);
+/// The fence mode of `fence`.
+pub const FM: Field = Field::new("fm", &[28..32]);
+
+/// The RV64G instruction formats.
+pub static RV64G: &[Format] = &[
+    Format::new("lui", 0x00000037, 0x0000007f, &[RD, U_IMM]),
+    Format::new("auipc", 0x00000017, 0x0000007f, &[RD, U_IMM]),
+    Format::new("jal", 0x0000006f, 0x0000007f, &[RD, J_IMM]),
+    Format::new("jalr", 0x00000067, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("beq", 0x00000063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("bne", 0x00001063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("blt", 0x00004063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("bge", 0x00005063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("bltu", 0x00006063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("bgeu", 0x00007063, 0x0000707f, &[RS1, RS2, B_IMM]),
+    Format::new("lb", 0x00000003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("lh", 0x00001003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("lw", 0x00002003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("ld", 0x00003003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("lbu", 0x00004003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("lhu", 0x00005003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("lwu", 0x00006003, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("sb", 0x00000023, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("sh", 0x00001023, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("sw", 0x00002023, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("sd", 0x00003023, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("addi", 0x00000013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("slti", 0x00002013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("sltiu", 0x00003013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("xori", 0x00004013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("ori", 0x00006013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("andi", 0x00007013, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("slli", 0x00001013, 0xfc00707f, &[RD, RS1, SHAMT]),
+    Format::new("srli", 0x00005013, 0xfc00707f, &[RD, RS1, SHAMT]),
+    Format::new("srai", 0x40005013, 0xfc00707f, &[RD, RS1, SHAMT]),
+    Format::new("add", 0x00000033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sub", 0x40000033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sll", 0x00001033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("slt", 0x00002033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sltu", 0x00003033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("xor", 0x00004033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("srl", 0x00005033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sra", 0x40005033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("or", 0x00006033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("and", 0x00007033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("addiw", 0x0000001b, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("slliw", 0x0000101b, 0xfe00707f, &[RD, RS1, SHAMTW]),
+    Format::new("srliw", 0x0000501b, 0xfe00707f, &[RD, RS1, SHAMTW]),
+    Format::new("sraiw", 0x4000501b, 0xfe00707f, &[RD, RS1, SHAMTW]),
+    Format::new("addw", 0x0000003b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("subw", 0x4000003b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sllw", 0x0000103b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("srlw", 0x0000503b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("sraw", 0x4000503b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fence.tso", 0x8330000f, 0xfff0707f, &[RD, RS1]),
+    Format::new("fence", 0x0000000f, 0x0000707f, &[RD, RS1, SUCC, PRED, FM]),
+    Format::new("ecall", 0x00000073, 0xffffffff, &[]),
+    Format::new("ebreak", 0x00100073, 0xffffffff, &[]),
+    Format::new("fence.i", 0x0000100f, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("csrrw", 0x00001073, 0x0000707f, &[RD, RS1, CSR]),
+    Format::new("csrrs", 0x00002073, 0x0000707f, &[RD, RS1, CSR]),
+    Format::new("csrrc", 0x00003073, 0x0000707f, &[RD, RS1, CSR]),
+    Format::new("csrrwi", 0x00005073, 0x0000707f, &[RD, ZIMM, CSR]),
+    Format::new("csrrsi", 0x00006073, 0x0000707f, &[RD, ZIMM, CSR]),
+    Format::new("csrrci", 0x00007073, 0x0000707f, &[RD, ZIMM, CSR]),
+    Format::new("mul", 0x02000033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("mulh", 0x02001033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("mulhsu", 0x02002033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("mulhu", 0x02003033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("div", 0x02004033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("divu", 0x02005033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("rem", 0x02006033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("remu", 0x02007033, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("mulw", 0x0200003b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("divw", 0x0200403b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("divuw", 0x0200503b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("remw", 0x0200603b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("remuw", 0x0200703b, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("lr.w", 0x1000202f, 0xf9f0707f, &[RD, RS1, RL, AQ]),
+    Format::new("sc.w", 0x1800202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoswap.w", 0x0800202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoadd.w", 0x0000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoxor.w", 0x2000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoand.w", 0x6000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoor.w", 0x4000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomin.w", 0x8000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomax.w", 0xa000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amominu.w", 0xc000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomaxu.w", 0xe000202f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("lr.d", 0x1000302f, 0xf9f0707f, &[RD, RS1, RL, AQ]),
+    Format::new("sc.d", 0x1800302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoswap.d", 0x0800302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoadd.d", 0x0000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoxor.d", 0x2000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoand.d", 0x6000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amoor.d", 0x4000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomin.d", 0x8000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomax.d", 0xa000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amominu.d", 0xc000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("amomaxu.d", 0xe000302f, 0xf800707f, &[RD, RS1, RS2, RL, AQ]),
+    Format::new("flw", 0x00002007, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("fsw", 0x00002027, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("fld", 0x00003007, 0x0000707f, &[RD, RS1, I_IMM]),
+    Format::new("fsd", 0x00003027, 0x0000707f, &[RS1, RS2, S_IMM]),
+    Format::new("fmadd.s", 0x00000043, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fmsub.s", 0x00000047, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fnmsub.s", 0x0000004b, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fnmadd.s", 0x0000004f, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fadd.s", 0x00000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fsub.s", 0x08000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fmul.s", 0x10000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fdiv.s", 0x18000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fsqrt.s", 0x58000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fsgnj.s", 0x20000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fsgnjn.s", 0x20001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fsgnjx.s", 0x20002053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fmin.s", 0x28000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fmax.s", 0x28001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("feq.s", 0xa0002053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("flt.s", 0xa0001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fle.s", 0xa0000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fcvt.w.s", 0xc0000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.s.w", 0xd0000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.wu.s", 0xc0100053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.s.wu", 0xd0100053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.l.s", 0xc0200053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.s.l", 0xd0200053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.lu.s", 0xc0300053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.s.lu", 0xd0300053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fmv.x.w", 0xe0000053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fclass.s", 0xe0001053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fmv.w.x", 0xf0000053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fmadd.d", 0x02000043, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fmsub.d", 0x02000047, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fnmsub.d", 0x0200004b, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fnmadd.d", 0x0200004f, 0x0600007f, &[RD, RS1, RS2, RS3, RM]),
+    Format::new("fadd.d", 0x02000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fsub.d", 0x0a000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fmul.d", 0x12000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fdiv.d", 0x1a000053, 0xfe00007f, &[RD, RS1, RS2, RM]),
+    Format::new("fsqrt.d", 0x5a000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fsgnj.d", 0x22000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fsgnjn.d", 0x22001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fsgnjx.d", 0x22002053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fmin.d", 0x2a000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fmax.d", 0x2a001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("feq.d", 0xa2002053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("flt.d", 0xa2001053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fle.d", 0xa2000053, 0xfe00707f, &[RD, RS1, RS2]),
+    Format::new("fcvt.w.d", 0xc2000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.d.w", 0xd2000053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.wu.d", 0xc2100053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.d.wu", 0xd2100053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.l.d", 0xc2200053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.d.l", 0xd2200053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.lu.d", 0xc2300053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.d.lu", 0xd2300053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fmv.x.d", 0xe2000053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fclass.d", 0xe2001053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fmv.d.x", 0xf2000053, 0xfff0707f, &[RD, RS1]),
+    Format::new("fcvt.s.d", 0x40100053, 0xfff0007f, &[RD, RS1, RM]),
+    Format::new("fcvt.d.s", 0x42000053, 0xfff0007f, &[RD, RS1, RM]),
+];
+
+/// The destination register of compressed instructions.
+pub const C_RD: Field = Field::new("rd", &[7..12]);
+/// The source register of `c.jr` and `c.jalr`.
+pub const C_RS1: Field = Field::new("rs1", &[7..12]);
+/// The second source register of compressed instructions.
+pub const C_RS2: Field = Field::new("rs2", &[2..7]);
+/// The compressed destination register `rd'` of loads and `c.addi4spn`.
+pub const C_RD_P: Field = Field::new("rd", &[2..5]).biased(8);
+/// The compressed base register `rs1'` of loads, stores and branches.
+pub const C_RS1_P: Field = Field::new("rs1", &[7..10]).biased(8);
+/// The compressed source register `rs2'` of stores and arithmetic.
+pub const C_RS2_P: Field = Field::new("rs2", &[2..5]).biased(8);
+/// The compressed register `rd'/rs1'` of arithmetic instructions.
+pub const C_RD_RS1_P: Field = Field::new("rd", &[7..10]).biased(8);
+/// The CI-type immediate `imm[5|4:0]`.
+pub const C_IMM: Field = Field::new("imm", &[2..7, 12..13]).signed();
+/// The shift amount of compressed immediate shifts.
+pub const C_SHAMT: Field = Field::new("shamt", &[2..7, 12..13]);
+/// The immediate of `c.lui`, as the sign-extended value it adds.
+pub const C_LUI_IMM: Field = Field::new("imm", &[2..7, 12..13]).signed().scaled(12);
+/// The immediate of `c.addi16sp`.
+pub const C_ADDI16SP_IMM: Field = Field::new("imm", &[6..7, 2..3, 5..6, 3..5, 12..13])
+    .signed()
+    .scaled(4);
+/// The immediate of `c.addi4spn`.
+pub const C_ADDI4SPN_IMM: Field = Field::new("imm", &[6..7, 5..6, 11..13, 7..11]).scaled(2);
+/// The offset of word loads and stores `c.lw` and `c.sw`.
+pub const C_W_IMM: Field = Field::new("imm", &[6..7, 10..13, 5..6]).scaled(2);
+/// The offset of doubleword loads and stores such as `c.ld` and `c.fsd`.
+pub const C_D_IMM: Field = Field::new("imm", &[10..13, 5..7]).scaled(3);
+/// The offset of `c.lwsp`.
+pub const C_LWSP_IMM: Field = Field::new("imm", &[4..7, 12..13, 2..4]).scaled(2);
+/// The offset of `c.ldsp` and `c.fldsp`.
+pub const C_LDSP_IMM: Field = Field::new("imm", &[5..7, 12..13, 2..5]).scaled(3);
+/// The offset of `c.swsp`.
+pub const C_SWSP_IMM: Field = Field::new("imm", &[9..13, 7..9]).scaled(2);
+/// The offset of `c.sdsp` and `c.fsdsp`.
+pub const C_SDSP_IMM: Field = Field::new("imm", &[10..13, 7..10]).scaled(3);
+/// The CJ-type jump offset `offset[11|4|9:8|10|6|7|3:1|5]`.
+pub const C_J_IMM: Field = Field::new(
+    "imm",
+    &[3..6, 11..12, 2..3, 7..8, 6..7, 9..11, 8..9, 12..13],
+)
+.signed()
+.scaled(1);
+/// The CB-type branch offset `offset[8|4:3]` and `offset[7:6|2:1|5]`.
+pub const C_B_IMM: Field = Field::new("imm", &[3..5, 10..12, 2..3, 5..7, 12..13])
+    .signed()
+    .scaled(1);
+
+/// The RV64C instruction formats.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::{decode, InstCodeI2};
+/// use portal_pc_asm_common::types::code::riscv::RV64C;
+///
+/// // c.addi a0, 1
+/// let inst = decode(RV64C, InstCodeI2(0x0505)).unwrap();
+/// assert_eq!(inst.format.name, "c.addi");
+/// assert_eq!(inst.operands().collect::<Vec<_>>(), [("rd", 10), ("imm", 1)]);
+///
+/// // c.ld a0, 8(s1)
+/// let inst = decode(RV64C, InstCodeI2(0x6488)).unwrap();
+/// assert_eq!(inst.format.name, "c.ld");
+/// assert_eq!(inst.operands().collect::<Vec<_>>(), [("rd", 10), ("rs1", 9), ("imm", 8)]);
+/// assert_eq!(inst.format.encode(&[10, 9, 8]), Ok(InstCodeI2(0x6488)));
+///
+/// assert_eq!(decode(RV64C, InstCodeI2(0x9002)).unwrap().format.name, "c.ebreak");
+/// assert!(decode(RV64C, InstCodeI2(0x8000)).is_none());
+/// ```
+pub static RV64C: &[Format<InstCodeI2>] = &[
+    Format::new("c.addi4spn", 0x0000, 0xe003, &[C_RD_P, C_ADDI4SPN_IMM]),
+    Format::new("c.fld", 0x2000, 0xe003, &[C_RD_P, C_RS1_P, C_D_IMM]),
+    Format::new("c.lw", 0x4000, 0xe003, &[C_RD_P, C_RS1_P, C_W_IMM]),
+    Format::new("c.ld", 0x6000, 0xe003, &[C_RD_P, C_RS1_P, C_D_IMM]),
+    Format::new("c.fsd", 0xa000, 0xe003, &[C_RS1_P, C_RS2_P, C_D_IMM]),
+    Format::new("c.sw", 0xc000, 0xe003, &[C_RS1_P, C_RS2_P, C_W_IMM]),
+    Format::new("c.sd", 0xe000, 0xe003, &[C_RS1_P, C_RS2_P, C_D_IMM]),
+    Format::new("c.nop", 0x0001, 0xffff, &[]),
+    Format::new("c.addi", 0x0001, 0xe003, &[C_RD, C_IMM]),
+    Format::new("c.addiw", 0x2001, 0xe003, &[C_RD, C_IMM]),
+    Format::new("c.li", 0x4001, 0xe003, &[C_RD, C_IMM]),
+    Format::new("c.addi16sp", 0x6101, 0xef83, &[C_ADDI16SP_IMM]),
+    Format::new("c.lui", 0x6001, 0xe003, &[C_RD, C_LUI_IMM]),
+    Format::new("c.srli", 0x8001, 0xec03, &[C_RD_RS1_P, C_SHAMT]),
+    Format::new("c.srai", 0x8401, 0xec03, &[C_RD_RS1_P, C_SHAMT]),
+    Format::new("c.andi", 0x8801, 0xec03, &[C_RD_RS1_P, C_IMM]),
+    Format::new("c.sub", 0x8c01, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.xor", 0x8c21, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.or", 0x8c41, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.and", 0x8c61, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.subw", 0x9c01, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.addw", 0x9c21, 0xfc63, &[C_RD_RS1_P, C_RS2_P]),
+    Format::new("c.j", 0xa001, 0xe003, &[C_J_IMM]),
+    Format::new("c.beqz", 0xc001, 0xe003, &[C_RS1_P, C_B_IMM]),
+    Format::new("c.bnez", 0xe001, 0xe003, &[C_RS1_P, C_B_IMM]),
+    Format::new("c.slli", 0x0002, 0xe003, &[C_RD, C_SHAMT]),
+    Format::new("c.fldsp", 0x2002, 0xe003, &[C_RD, C_LDSP_IMM]),
+    Format::new("c.lwsp", 0x4002, 0xe003, &[C_RD, C_LWSP_IMM]),
+    Format::new("c.ldsp", 0x6002, 0xe003, &[C_RD, C_LDSP_IMM]),
+    Format::new("c.jr", 0x8002, 0xf07f, &[C_RS1]),
+    Format::new("c.mv", 0x8002, 0xf003, &[C_RD, C_RS2]),
+    Format::new("c.ebreak", 0x9002, 0xffff, &[]),
+    Format::new("c.jalr", 0x9002, 0xf07f, &[C_RS1]),
+    Format::new("c.add", 0x9002, 0xf003, &[C_RD, C_RS2]),
+    Format::new("c.fsdsp", 0xa002, 0xe003, &[C_RS2, C_SDSP_IMM]),
+    Format::new("c.swsp", 0xc002, 0xe003, &[C_RS2, C_SWSP_IMM]),
+    Format::new("c.sdsp", 0xe002, 0xe003, &[C_RS2, C_SDSP_IMM]),
+];
+/// Returns the length in bytes of the instruction starting at `code`: 2 for
+/// a compressed instruction and 4 for a 32-bit one.
+///
+/// Only the first two bytes are examined. Returns `None` if fewer are
+/// available or the instruction uses one of the reserved longer encodings.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::riscv::inst_len;
+///
+/// assert_eq!(inst_len(&[0x05, 0x05]), Some(2)); // c.addi a0, 1
+/// assert_eq!(inst_len(&[0x13, 0x05, 0x15, 0x00]), Some(4)); // addi a0, a0, 1
+/// assert_eq!(inst_len(&[0x13]), None);
+/// ```
+pub fn inst_len(code: &[u8]) -> Option<usize> {
+    let &[lo, _, ..] = code else {
+        return None;
+    };
+    match lo & 0x1f {
+        0x1f => None,
+        x if x & 3 == 3 => Some(4),
+        _ => Some(2),
+    }
+}
+
+/// Returns the length in bytes of the next instruction of `input`, as
+/// [`inst_len`] does for its code.
+///
+/// Splitting the input at each length walks a stream mixing compressed and
+/// 32-bit instructions.
+///
+/// # Examples
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use portal_pc_asm_common::types::code::riscv::next_inst_len;
+/// use portal_pc_asm_common::types::perms::{InputRef, Perms};
+///
+/// // c.addi a0, 1; addi a0, a0, 1; c.nop
+/// let code = [0x05, 0x05, 0x13, 0x05, 0x15, 0x00, 0x01, 0x00];
+/// let bits = bits![1; 8];
+/// let mut input = InputRef::new(&code, Perms { r: bits, w: bits, x: bits, nj: bits }).unwrap();
+///
+/// let mut lens = vec![];
+/// while let Some(len) = next_inst_len(&input) {
+///     lens.push(len);
+///     input = input.subref(len..);
+/// }
+/// assert_eq!(lens, [2, 4, 2]);
+/// ```
+pub fn next_inst_len(input: &InputRef<'_>) -> Option<usize> {
+    inst_len(input.code)
+}
@@ -0,0 +1,127 @@
+//! Immediate operand encodability.
+//!
+//! Rewriters use these queries to decide whether a [`Constant`] can be
+//! encoded directly in an instruction or has to be materialized in a register
+//! first. The architecture submodules return raw field values, ready to be
+//! placed with [`InstCodeI4::with`](super::code::InstCodeI4::with), and a
+//! `materialize` function planning the instructions that load a constant
+//! which cannot be encoded directly.
+
+use super::value::Constant;
+
+pub mod aarch64;
+pub mod arm;
+pub mod riscv;
+pub mod x86_64;
+
+/// Returns `true` if `c`, read as a 512-bit two's complement number, fits in a
+/// signed field of `bits` bits.
+///
+/// Constants produced for narrower operations should be sign-extended first
+/// with [`Constant::extend`].
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::fits_signed;
+/// use portal_pc_asm_common::types::value::Constant;
+///
+/// assert!(fits_signed(&Constant::from(-2048i64), 12));
+/// assert!(!fits_signed(&Constant::from(2048i64), 12));
+/// assert!(fits_signed(&Constant::from(2047u64), 12));
+/// ```
+pub fn fits_signed(c: &Constant, bits: u32) -> bool {
+    if bits == 0 {
+        return false;
+    }
+    let top = *c >> (bits - 1);
+    top.is_zero() || top == Constant::MAX >> (bits - 1)
+}
+
+/// Returns `true` if `c` fits in an unsigned field of `bits` bits.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::fits_unsigned;
+/// use portal_pc_asm_common::types::value::Constant;
+///
+/// assert!(fits_unsigned(&Constant::from(4095u64), 12));
+/// assert!(!fits_unsigned(&Constant::from(4096u64), 12));
+/// assert!(!fits_unsigned(&Constant::from(-1i64), 12));
+/// ```
+pub fn fits_unsigned(c: &Constant, bits: u32) -> bool {
+    (*c >> bits).is_zero()
+}
+
+/// The most instructions a materialization sequence can take.
+pub const MAX_OPS: usize = 8;
+
+/// A short instruction sequence, such as one loading a constant.
+///
+/// Holds up to [`MAX_OPS`] operations without allocating.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct OpList<T> {
+    ops: [Option<T>; MAX_OPS],
+    len: usize,
+}
+impl<T: Copy> OpList<T> {
+    /// Creates an empty list.
+    pub const fn new() -> Self {
+        OpList {
+            ops: [None; MAX_OPS],
+            len: 0,
+        }
+    }
+
+    /// Appends `op`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the list already holds [`MAX_OPS`] operations.
+    pub fn push(&mut self, op: T) {
+        self.ops[self.len] = Some(op);
+        self.len += 1;
+    }
+
+    /// Returns the number of operations.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list holds no operations.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the operations, in execution order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        self.ops[..self.len].iter().map(|op| op.as_ref().unwrap())
+    }
+}
+impl<T: Copy> Default for OpList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Copy> Extend<T> for OpList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for op in iter {
+            self.push(op);
+        }
+    }
+}
+impl<T: Copy> FromIterator<T> for OpList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+impl<T: Copy> IntoIterator for OpList<T> {
+    type Item = T;
+    type IntoIter = core::iter::Flatten<core::array::IntoIter<Option<T>, MAX_OPS>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.into_iter().flatten()
+    }
+}
@@ -0,0 +1,276 @@
+//! AArch64 immediates.
+
+use core::iter::once;
+
+use super::super::code::InstCodeI4;
+use super::super::reg::Reg;
+use super::super::value::{Bitness, Constant};
+use super::OpList;
+
+/// The fields of an AArch64 logical (bitmask) immediate, as used by `and`,
+/// `orr`, `eor` and `tst`.
+///
+/// In the instruction, `n` is bit 22, `immr` bits 16..22 and `imms` bits
+/// 10..16.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::code::InstCodeI4;
+/// use portal_pc_asm_common::types::imm::aarch64::LogicalImm;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// let imm = LogicalImm::encode(&Constant::from(0x00ff00ff00ff00ffu64), Bitness::I64).unwrap();
+/// assert_eq!((imm.n, imm.immr, imm.imms), (0, 0, 0b100111));
+/// assert_eq!(imm.decode(Bitness::I64), Some(Constant::from(0x00ff00ff00ff00ffu64)));
+///
+/// // and x0, x1, #0x00ff00ff00ff00ff
+/// let and = imm.apply(InstCodeI4(0x92000020));
+/// assert_eq!(and, InstCodeI4(0x92009c20));
+///
+/// assert_eq!(LogicalImm::encode(&Constant::ZERO, Bitness::I64), None);
+/// assert_eq!(LogicalImm::encode(&Constant::from(0x1234u64), Bitness::I64), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct LogicalImm {
+    /// Set for 64-bit element sizes
+    pub n: u32,
+    /// The rotate amount
+    pub immr: u32,
+    /// The element size and the number of set bits
+    pub imms: u32,
+}
+impl LogicalImm {
+    /// Encodes the low `width` bits of `c` as a logical immediate of a
+    /// `width`-bit instruction, which must be 32 or 64 bits.
+    ///
+    /// Returns `None` if the value is not a replicated, rotated run of ones;
+    /// all-zero and all-one values cannot be encoded.
+    pub fn encode(c: &Constant, width: Bitness) -> Option<Self> {
+        let low = c.truncate(width).data[0];
+        let mut imm = match width {
+            Bitness::I32 => low | low << 32,
+            Bitness::I64 => low,
+            _ => return None,
+        };
+        if imm == 0 || imm == u64::MAX {
+            return None;
+        }
+        // Find the smallest element size that replicates to the value.
+        let mut size = 64;
+        while size > 2 {
+            let half = size / 2;
+            let mask = (1u64 << half) - 1;
+            if imm & mask != (imm >> half) & mask {
+                break;
+            }
+            size = half;
+        }
+        let mask = u64::MAX >> (64 - size);
+        imm &= mask;
+        // Find the rotation and length of the run of ones in the element.
+        let (rotation, ones) = if is_shifted_mask(imm) {
+            let tz = imm.trailing_zeros();
+            (tz, (imm >> tz).trailing_ones())
+        } else {
+            let imm = imm | !mask;
+            if !is_shifted_mask(!imm) {
+                return None;
+            }
+            let lo = imm.leading_ones();
+            (64 - lo, lo + imm.trailing_ones() - (64 - size))
+        };
+        let immr = (size - rotation) & (size - 1);
+        let nimms = (!(size - 1) << 1) | (ones - 1);
+        Some(LogicalImm {
+            n: ((nimms >> 6) & 1) ^ 1,
+            immr,
+            imms: nimms & 0x3f,
+        })
+    }
+
+    /// Returns the value this immediate stands for in a `width`-bit
+    /// instruction, or `None` if the encoding is reserved.
+    pub fn decode(self, width: Bitness) -> Option<Constant> {
+        if self.n > 1 || self.immr > 0x3f || self.imms > 0x3f {
+            return None;
+        }
+        match width {
+            Bitness::I32 if self.n == 0 => {}
+            Bitness::I64 => {}
+            _ => return None,
+        }
+        let len = ((self.n << 6) | (!self.imms & 0x3f)).checked_ilog2()?;
+        if len < 1 {
+            return None;
+        }
+        let size = 1u32 << len;
+        let r = self.immr & (size - 1);
+        let s = self.imms & (size - 1);
+        if s == size - 1 {
+            return None;
+        }
+        let mask = u64::MAX >> (64 - size);
+        let mut pattern = (1u64 << (s + 1)) - 1;
+        if r != 0 {
+            pattern = ((pattern >> r) | (pattern << (size - r))) & mask;
+        }
+        let mut value = pattern;
+        let mut filled = size;
+        while filled < 64 {
+            value |= value << filled;
+            filled *= 2;
+        }
+        Some(Constant::from(value).truncate(width))
+    }
+
+    /// Returns `code` with the `n`, `immr` and `imms` fields set.
+    pub fn apply(self, code: InstCodeI4) -> InstCodeI4 {
+        code.with(once(22..23), self.n)
+            .with(once(16..22), self.immr)
+            .with(once(10..16), self.imms)
+    }
+}
+
+/// An instruction of an AArch64 constant materialization sequence.
+///
+/// `hw` is the `hw` field, the 16-bit halfword the immediate is shifted
+/// into.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum Op {
+    /// `movz rd, #imm16, lsl #(16 * hw)`
+    MovZ { rd: Reg, imm16: u32, hw: u32 },
+    /// `movn rd, #imm16, lsl #(16 * hw)`
+    MovN { rd: Reg, imm16: u32, hw: u32 },
+    /// `movk rd, #imm16, lsl #(16 * hw)`
+    MovK { rd: Reg, imm16: u32, hw: u32 },
+    /// `orr rd, zr, #imm`
+    Orr { rd: Reg, imm: LogicalImm },
+}
+
+/// Returns a short sequence loading the low `width` bits of `c` into `rd`,
+/// where `width` is 32 or 64.
+///
+/// The sequence is the shortest of a `movz` or `movn` followed by a `movk`
+/// for each remaining halfword, and an `orr` of a logical immediate, which
+/// may also be followed by `movk`s for the halfwords it gets wrong. Returns
+/// `None` for other widths.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::aarch64::{materialize, LogicalImm, Op};
+/// use portal_pc_asm_common::types::reg::aarch64::x;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// let ops = |v: u64| {
+///     let ops = materialize(&Constant::from(v), x(0), Bitness::I64).unwrap();
+///     ops.iter().copied().collect::<Vec<_>>()
+/// };
+///
+/// assert_eq!(
+///     ops(0x1234_0000_5678),
+///     [
+///         Op::MovZ { rd: x(0), imm16: 0x5678, hw: 0 },
+///         Op::MovK { rd: x(0), imm16: 0x1234, hw: 2 },
+///     ]
+/// );
+/// assert_eq!(ops(0xffff_ffff_fffe_ffff), [Op::MovN { rd: x(0), imm16: 1, hw: 1 }]);
+/// assert_eq!(ops(0x5555_5555_5555_5555).len(), 1);
+/// assert_eq!(ops(0x5555_1234_5555_5555).len(), 2);
+/// ```
+pub fn materialize(c: &Constant, rd: Reg, width: Bitness) -> Option<OpList<Op>> {
+    let halfwords = match width {
+        Bitness::I32 => 2,
+        Bitness::I64 => 4,
+        _ => return None,
+    };
+    let v = c.truncate(width).data[0];
+    let chunk = |v: u64, hw: u32| (v >> (16 * hw)) as u32 & 0xffff;
+
+    let mut best = OpList::new();
+    for (fill, first) in [(0, MovKind::Z), (0xffff, MovKind::N)] {
+        let mut ops = OpList::new();
+        let mut rest = (0..halfwords).filter(|&hw| chunk(v, hw) != fill);
+        let hw = rest.next().unwrap_or(0);
+        ops.push(match first {
+            MovKind::Z => Op::MovZ {
+                rd,
+                imm16: chunk(v, hw),
+                hw,
+            },
+            MovKind::N => Op::MovN {
+                rd,
+                imm16: chunk(!v, hw),
+                hw,
+            },
+        });
+        ops.extend(rest.map(|hw| Op::MovK {
+            rd,
+            imm16: chunk(v, hw),
+            hw,
+        }));
+        if best.is_empty() || ops.len() < best.len() {
+            best = ops;
+        }
+    }
+    // Try an `orr` of a logical immediate agreeing with the value in as many
+    // halfwords as possible, guessing the others from the value's halfwords.
+    let mut guesses = OpList::<u32>::new();
+    for hw in 0..halfwords {
+        if !guesses.iter().any(|&g| g == chunk(v, hw)) {
+            guesses.push(chunk(v, hw));
+        }
+    }
+    for wrong in 0u32..1 << halfwords {
+        if wrong.count_ones() + 1 >= best.len() as u32 {
+            continue;
+        }
+        let fixed = (0..halfwords).filter(|hw| wrong & 1 << hw != 0);
+        let mut found = None;
+        let combos = guesses.len().pow(wrong.count_ones());
+        for mut combo in 0..combos {
+            let mut guess = v;
+            for hw in fixed.clone() {
+                let g = *guesses.iter().nth(combo % guesses.len()).unwrap();
+                combo /= guesses.len();
+                guess = (guess & !(0xffff << (16 * hw))) | (g as u64) << (16 * hw);
+            }
+            if let Some(imm) = LogicalImm::encode(&Constant::from(guess), width) {
+                found = Some((imm, guess));
+                break;
+            }
+        }
+        if let Some((imm, guess)) = found {
+            let mut ops = OpList::new();
+            ops.push(Op::Orr { rd, imm });
+            ops.extend(
+                fixed
+                    .filter(|&hw| chunk(guess, hw) != chunk(v, hw))
+                    .map(|hw| Op::MovK {
+                        rd,
+                        imm16: chunk(v, hw),
+                        hw,
+                    }),
+            );
+            if ops.len() < best.len() {
+                best = ops;
+            }
+        }
+    }
+    Some(best)
+}
+
+/// The first instruction of a move-wide sequence.
+#[derive(Clone, Copy)]
+enum MovKind {
+    Z,
+    N,
+}
+
+/// Returns `true` if the set bits of `v` form one nonempty contiguous run.
+fn is_shifted_mask(v: u64) -> bool {
+    let filled = v | v.wrapping_sub(1);
+    v != 0 && filled.wrapping_add(1) & filled == 0
+}
@@ -0,0 +1,36 @@
+//! 32-bit ARM (A32) immediates.
+
+use super::super::value::{Bitness, Constant};
+
+/// Encodes the low 32 bits of `c` as an A32 modified immediate: an 8-bit
+/// value rotated right by twice a 4-bit amount.
+///
+/// Returns the 12-bit field, rotation in bits 8..12 and value in bits 0..8,
+/// using the smallest rotation that works, or `None` if the value cannot be
+/// encoded.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::arm::{decode_rotated, encode_rotated};
+/// use portal_pc_asm_common::types::value::Constant;
+///
+/// assert_eq!(encode_rotated(&Constant::from(0xffu32)), Some(0x0ff));
+/// assert_eq!(encode_rotated(&Constant::from(0x3fcu32)), Some(0xfff));
+/// assert_eq!(encode_rotated(&Constant::from(0xf000000fu32)), Some(0x2ff));
+/// assert_eq!(encode_rotated(&Constant::from(0x101u32)), None);
+///
+/// assert_eq!(decode_rotated(0x2ff), Constant::from(0xf000000fu32));
+/// ```
+pub fn encode_rotated(c: &Constant) -> Option<u32> {
+    let v = c.truncate(Bitness::I32).data[0] as u32;
+    (0..16).find_map(|rot| {
+        let imm8 = v.rotate_left(2 * rot);
+        (imm8 <= 0xff).then_some(rot << 8 | imm8)
+    })
+}
+
+/// Returns the value of the 12-bit A32 modified immediate `field`.
+pub fn decode_rotated(field: u32) -> Constant {
+    Constant::from((field & 0xff).rotate_right(2 * ((field >> 8) & 0xf)))
+}
@@ -0,0 +1,238 @@
+//! RISC-V immediates.
+
+use super::super::ops::Ext;
+use super::super::reg::{riscv::ZERO, Reg};
+use super::super::value::{Bitness, Constant};
+use super::{fits_signed, OpList};
+
+/// Encodes the low `xlen` bits of `c` as a 12-bit I-type immediate, as used
+/// by `addi`, returning the raw field value.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::riscv::imm12;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// assert_eq!(imm12(&Constant::from(-1i64), Bitness::I64), Some(0xfff));
+/// assert_eq!(imm12(&Constant::from(0xffffffffu32), Bitness::I32), Some(0xfff));
+/// assert_eq!(imm12(&Constant::from(0xffffffffu32), Bitness::I64), None);
+/// ```
+pub fn imm12(c: &Constant, xlen: Bitness) -> Option<u32> {
+    let v = c.extend(xlen, Bitness::I512, Ext::Sign);
+    fits_signed(&v, 12).then_some(v.data[0] as u32 & 0xfff)
+}
+
+/// The fields of a `lui`/`addi` pair loading a constant.
+///
+/// `hi20` goes in bits 12..32 of the `lui` and `lo12` in bits 20..32 of the
+/// `addi`.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::riscv::LuiAddi;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// let c = Constant::from(0x12345fffu32);
+/// let split = LuiAddi::split(&c, Bitness::I64).unwrap();
+/// assert_eq!((split.hi20, split.lo12), (0x12346, 0xfff));
+/// assert_eq!(split.value(Bitness::I64), c);
+///
+/// // On RV64, lui sign-extends, so not every 32-bit value can be loaded.
+/// assert!(LuiAddi::split(&Constant::from(0x7fffffffu32), Bitness::I64).is_none());
+/// assert!(LuiAddi::split(&Constant::from(0x7fffffffu32), Bitness::I32).is_some());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct LuiAddi {
+    /// The upper immediate of the `lui`
+    pub hi20: u32,
+    /// The immediate of the `addi`
+    pub lo12: u32,
+}
+impl LuiAddi {
+    /// Splits the low `xlen` bits of `c` into a `lui`/`addi` pair, where
+    /// `xlen` is 32 or 64.
+    ///
+    /// Returns `None` if no pair produces the value in an `xlen`-bit
+    /// register.
+    pub fn split(c: &Constant, xlen: Bitness) -> Option<Self> {
+        let v = match xlen {
+            Bitness::I32 => c.to_i64(Bitness::I32)? as i32 as i64,
+            Bitness::I64 => c.to_i64(Bitness::I64)?,
+            _ => return None,
+        };
+        let hi = match xlen {
+            Bitness::I32 => (v as i32).wrapping_add(0x800) >> 12,
+            _ => i32::try_from((v + 0x800) >> 12).ok()?,
+        };
+        if !(-(1 << 19)..1 << 19).contains(&hi) {
+            return None;
+        }
+        let lo = v.wrapping_sub((hi as i64) << 12) as u32 & 0xfff;
+        Some(LuiAddi {
+            hi20: hi as u32 & 0xfffff,
+            lo12: lo,
+        })
+    }
+
+    /// Returns the value the pair leaves in an `xlen`-bit register.
+    pub fn value(self, xlen: Bitness) -> Constant {
+        let hi = ((self.hi20 << 12) as i32) as i64;
+        let lo = (((self.lo12 << 20) as i32) >> 20) as i64;
+        Constant::from(hi.wrapping_add(lo)).truncate(xlen)
+    }
+}
+
+/// An instruction of a RISC-V constant materialization sequence.
+///
+/// Immediates are raw field values: `hi20` for bits 12..32 of `lui`,
+/// `imm12` for bits 20..32 of the I-type instructions.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum Op {
+    /// `lui rd, hi20`
+    Lui { rd: Reg, hi20: u32 },
+    /// `addi rd, rs1, imm12`
+    Addi { rd: Reg, rs1: Reg, imm12: u32 },
+    /// `addiw rd, rs1, imm12`
+    Addiw { rd: Reg, rs1: Reg, imm12: u32 },
+    /// `slli rd, rs1, shamt`
+    Slli { rd: Reg, rs1: Reg, shamt: u32 },
+    /// `srli rd, rs1, shamt`
+    Srli { rd: Reg, rs1: Reg, shamt: u32 },
+}
+
+/// Returns a short base-ISA sequence loading the low `xlen` bits of `c` into
+/// `rd`, where `xlen` is 32 or 64.
+///
+/// Builds `lui`/`addi(w)` pairs, extended on RV64 by `slli`/`addi` steps,
+/// and tries shifting out trailing or leading zeros when that is shorter.
+///
+/// Returns `None` for other values of `xlen`.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::riscv::{materialize, Op};
+/// use portal_pc_asm_common::types::reg::riscv::{A0, ZERO};
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// let ops = materialize(&Constant::from(0x12345678u32), A0, Bitness::I64).unwrap();
+/// assert_eq!(
+///     ops.iter().copied().collect::<Vec<_>>(),
+///     [
+///         Op::Lui { rd: A0, hi20: 0x12345 },
+///         Op::Addiw { rd: A0, rs1: A0, imm12: 0x678 },
+///     ]
+/// );
+///
+/// // li a0, -1; srli a0, a0, 32
+/// let ops = materialize(&Constant::from(0xffffffffu32), A0, Bitness::I64).unwrap();
+/// assert_eq!(
+///     ops.iter().copied().collect::<Vec<_>>(),
+///     [
+///         Op::Addi { rd: A0, rs1: ZERO, imm12: 0xfff },
+///         Op::Srli { rd: A0, rs1: A0, shamt: 32 },
+///     ]
+/// );
+///
+/// let ops = materialize(&Constant::from(0x123456789abcdef0u64), A0, Bitness::I64).unwrap();
+/// assert_eq!(ops.len(), 8);
+/// ```
+pub fn materialize(c: &Constant, rd: Reg, xlen: Bitness) -> Option<OpList<Op>> {
+    let rv64 = match xlen {
+        Bitness::I32 => false,
+        Bitness::I64 => true,
+        _ => return None,
+    };
+    let v = c.to_i64(xlen)?;
+    let mut best = Steps::new();
+    steps(v, rv64, &mut best);
+    if v & 0xfff != 0 && v & 1 == 0 && best.len() >= 2 {
+        let zeros = v.trailing_zeros();
+        let mut alt = Steps::new();
+        steps(v >> zeros, rv64, &mut alt);
+        if alt.len() + 1 < best.len() {
+            alt.push(Step::Slli(zeros));
+            best = alt;
+        }
+    }
+    if best.len() > 2 && v > 0 {
+        let zeros = v.leading_zeros();
+        let shifted = (v as u64) << zeros;
+        for fill in [u64::MAX >> (64 - zeros), 0] {
+            let mut alt = Steps::new();
+            steps((shifted | fill) as i64, rv64, &mut alt);
+            if alt.len() + 1 < best.len() {
+                alt.push(Step::Srli(zeros));
+                best = alt;
+            }
+        }
+    }
+    let mut rs1 = ZERO;
+    Some(
+        best.into_iter()
+            .map(|step| {
+                let op = match step {
+                    Step::Lui(hi20) => Op::Lui { rd, hi20 },
+                    Step::Addi(imm12) => Op::Addi { rd, rs1, imm12 },
+                    Step::Addiw(imm12) => Op::Addiw { rd, rs1, imm12 },
+                    Step::Slli(shamt) => Op::Slli { rd, rs1, shamt },
+                    Step::Srli(shamt) => Op::Srli { rd, rs1, shamt },
+                };
+                rs1 = rd;
+                op
+            })
+            .collect(),
+    )
+}
+
+/// An instruction of a sequence under construction, before registers are
+/// assigned.
+#[derive(Clone, Copy)]
+enum Step {
+    Lui(u32),
+    Addi(u32),
+    Addiw(u32),
+    Slli(u32),
+    Srli(u32),
+}
+type Steps = OpList<Step>;
+
+/// Appends a sequence producing `v` to `out`.
+fn steps(v: i64, rv64: bool, out: &mut Steps) {
+    if let Ok(v) = i32::try_from(v) {
+        let hi20 = (v.wrapping_add(0x800) >> 12) as u32 & 0xfffff;
+        let lo12 = v as u32 & 0xfff;
+        if hi20 != 0 {
+            out.push(Step::Lui(hi20));
+        }
+        if lo12 != 0 || hi20 == 0 {
+            out.push(match rv64 && hi20 != 0 {
+                true => Step::Addiw(lo12),
+                false => Step::Addi(lo12),
+            });
+        }
+        return;
+    }
+    let lo12 = (v << 52) >> 52;
+    let mut hi = v.wrapping_sub(lo12);
+    let mut shift = 0;
+    if i32::try_from(hi).is_err() {
+        shift = hi.trailing_zeros();
+        hi >>= shift;
+        // Leave the low 12 bits to a `lui` if the rest does not fit an `addi`.
+        if shift > 12 && !(-2048..2048).contains(&hi) && i32::try_from(hi << 12).is_ok() {
+            shift -= 12;
+            hi <<= 12;
+        }
+    }
+    steps(hi, rv64, out);
+    if shift != 0 {
+        out.push(Step::Slli(shift));
+    }
+    if lo12 != 0 {
+        out.push(Step::Addi(lo12 as u32 & 0xfff));
+    }
+}
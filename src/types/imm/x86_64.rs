@@ -0,0 +1,106 @@
+//! x86-64 immediates.
+
+use super::super::ops::Ext;
+use super::super::reg::Reg;
+use super::super::value::{Bitness, Constant};
+use super::{fits_signed, OpList};
+
+/// Returns the sign-extended `bits`-bit immediate standing for the low
+/// `operand` bits of `c`, if there is one.
+fn sign_extended_imm(c: &Constant, operand: Bitness, bits: u32) -> Option<u64> {
+    if operand > Bitness::I64 {
+        return None;
+    }
+    let v = c.extend(operand, Bitness::I512, Ext::Sign);
+    fits_signed(&v, bits).then_some(v.data[0] & (u64::MAX >> (64 - bits)))
+}
+
+/// Encodes the low `operand` bits of `c` as an 8-bit immediate, which the
+/// processor sign-extends to the operand size.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::x86_64::imm8;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// assert_eq!(imm8(&Constant::from(-1i64), Bitness::I64), Some(0xff));
+/// assert_eq!(imm8(&Constant::from(0xffffu16), Bitness::I16), Some(0xff));
+/// assert_eq!(imm8(&Constant::from(0xffu8), Bitness::I32), None);
+/// assert_eq!(imm8(&Constant::from(0xffu8), Bitness::I8), Some(0xff));
+/// ```
+pub fn imm8(c: &Constant, operand: Bitness) -> Option<u8> {
+    sign_extended_imm(c, operand, 8).map(|v| v as u8)
+}
+
+/// Encodes the low `operand` bits of `c` as a 32-bit immediate, which the
+/// processor sign-extends for 64-bit operands.
+///
+/// Returns `None` for operands narrower than 32 bits, which take 8- or 16-bit
+/// immediates instead.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::x86_64::imm32;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// assert_eq!(imm32(&Constant::from(-2i64), Bitness::I64), Some(0xfffffffe));
+/// assert_eq!(imm32(&Constant::from(0xfffffffeu32), Bitness::I64), None);
+/// assert_eq!(imm32(&Constant::from(0xfffffffeu32), Bitness::I32), Some(0xfffffffe));
+/// ```
+pub fn imm32(c: &Constant, operand: Bitness) -> Option<u32> {
+    if operand < Bitness::I32 {
+        return None;
+    }
+    sign_extended_imm(c, operand, 32).map(|v| v as u32)
+}
+
+/// An instruction of an x86-64 constant materialization sequence.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum Op {
+    /// `mov r32, imm32`, which zero-extends into the full register
+    MovImm32 { rd: Reg, imm: u32 },
+    /// `mov r/m64, imm32`, which sign-extends the immediate
+    MovSignExtended { rd: Reg, imm: u32 },
+    /// `mov r64, imm64`
+    MovImm64 { rd: Reg, imm: u64 },
+}
+
+/// Returns the shortest `mov` loading the low `width` bits of `c` into `rd`,
+/// where `width` is at most 64.
+///
+/// Prefers the 5-byte `mov r32, imm32` whenever the value zero-extends from
+/// 32 bits, then the 7-byte sign-extending `mov r/m64, imm32`, and uses the
+/// 10-byte `mov r64, imm64` only for the remaining values. Narrower widths
+/// always use `mov r32, imm32`. Returns `None` for wider widths.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::imm::x86_64::{materialize, Op};
+/// use portal_pc_asm_common::types::reg::x86_64::RAX;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant};
+///
+/// let op = |c: Constant| *materialize(&c, RAX, Bitness::I64).unwrap().iter().next().unwrap();
+///
+/// assert_eq!(op(Constant::from(0xffffffffu32)), Op::MovImm32 { rd: RAX, imm: 0xffffffff });
+/// assert_eq!(op(Constant::from(-1i64)), Op::MovSignExtended { rd: RAX, imm: 0xffffffff });
+/// assert_eq!(op(Constant::from(1u64 << 32)), Op::MovImm64 { rd: RAX, imm: 1 << 32 });
+/// ```
+pub fn materialize(c: &Constant, rd: Reg, width: Bitness) -> Option<OpList<Op>> {
+    if width > Bitness::I64 {
+        return None;
+    }
+    let v = c.truncate(width).data[0];
+    let mut ops = OpList::new();
+    ops.push(if let Ok(imm) = u32::try_from(v) {
+        Op::MovImm32 { rd, imm }
+    } else if let Some(imm) = imm32(c, Bitness::I64) {
+        Op::MovSignExtended { rd, imm }
+    } else {
+        Op::MovImm64 { rd, imm: v }
+    });
+    Some(ops)
+}
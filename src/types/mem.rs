@@ -3,6 +3,7 @@
 //! This module provides types for representing memory operations with
 //! explicit size information.
 
+use super::value::Bitness;
 use super::*;
 
 /// The size of a memory access operation.
@@ -65,3 +66,47 @@ pub struct MemorySized<T> {
     /// The size of the memory access
     pub size: MemorySize,
 }
+impl From<MemorySize> for Bitness {
+    /// Converts a memory access size to the bitness of the accessed value.
+    fn from(value: MemorySize) -> Self {
+        match value {
+            MemorySize::_8 => Bitness::I8,
+            MemorySize::_16 => Bitness::I16,
+            MemorySize::_32 => Bitness::I32,
+            MemorySize::_64 => Bitness::I64,
+            MemorySize::_128 => Bitness::I128,
+            MemorySize::_256 => Bitness::I256,
+            MemorySize::_512 => Bitness::I512,
+        }
+    }
+}
+impl TryFrom<Bitness> for MemorySize {
+    type Error = Bitness;
+    /// Converts a bitness to the matching memory access size.
+    ///
+    /// Fails, returning the bitness, for widths below 8 bits or above 512
+    /// bits, which no memory access has.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::mem::MemorySize;
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(MemorySize::try_from(Bitness::I32), Ok(MemorySize::_32));
+    /// assert_eq!(Bitness::from(MemorySize::_32), Bitness::I32);
+    /// assert_eq!(MemorySize::try_from(Bitness::I1), Err(Bitness::I1));
+    /// ```
+    fn try_from(value: Bitness) -> Result<Self, Self::Error> {
+        Ok(match value.log2 {
+            3 => MemorySize::_8,
+            4 => MemorySize::_16,
+            5 => MemorySize::_32,
+            6 => MemorySize::_64,
+            7 => MemorySize::_128,
+            8 => MemorySize::_256,
+            9 => MemorySize::_512,
+            _ => return Err(value),
+        })
+    }
+}
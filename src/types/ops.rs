@@ -4,7 +4,9 @@
 //! operations at the assembly level, along with related concepts like signedness
 //! and endianness.
 
+use super::value::{Bitness, Constant};
 use super::*;
+use core::cmp::Ordering;
 
 /// An arithmetic or logical operation.
 ///
@@ -54,6 +56,341 @@ pub enum Arith {
     /// Rotate right operation with specified signedness
     Rotr(Sign),
 }
+impl Arith {
+    /// Evaluates this operation on two constants at the given bit width.
+    ///
+    /// Both operands are truncated to `bitness` before evaluation and the
+    /// result is truncated to `bitness`, so every operation wraps using two's
+    /// complement arithmetic. Bits of the result above the width are zero.
+    ///
+    /// Edge cases are resolved as follows:
+    ///
+    /// - [`Arith::Mul`] yields the low half of the product; use
+    ///   [`Constant::widening_mul`] for the full product.
+    /// - Shift and rotate amounts are taken modulo the bit width, as RISC-V,
+    ///   AArch64 and WebAssembly do.
+    /// - The [`Sign`] of a rotation does not affect its result.
+    /// - Division edge cases follow [`DivPolicy::RiscV`]; use
+    ///   [`Arith::try_eval`] to select another policy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Arith, Sign};
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    ///
+    /// assert!(Arith::Add.eval(&c(0xff), &c(2), byte) == c(1));
+    /// assert!(Arith::Sub.eval(&c(0), &c(1), byte) == c(0xff));
+    /// assert!(Arith::Shr(Sign::Signed).eval(&c(0x80), &c(7), byte) == c(0xff));
+    /// assert!(Arith::Div(Sign::Signed).eval(&c(0xf9), &c(2), byte) == c(0xfd));
+    /// assert!(Arith::Rotl(Sign::Unsigned).eval(&c(0x81), &c(9), byte) == c(0x03));
+    ///
+    /// let full = Bitness { log2: 9 };
+    /// let sar = |v: &Constant, n| Arith::Shr(Sign::Signed).eval(v, &c(n), full);
+    /// assert!(sar(&Constant::MAX, 4) == Constant::MAX);
+    /// assert!(sar(&(Constant::ONE << 511), 4) == Constant::MAX << 507);
+    /// assert!(sar(&(Constant::ONE << 510), 4) == Constant::ONE << 506);
+    /// ```
+    pub fn eval(self, lhs: &Constant, rhs: &Constant, bitness: Bitness) -> Constant {
+        match self.try_eval(lhs, rhs, bitness, DivPolicy::RiscV) {
+            Ok(v) => v,
+            Err(_) => unreachable!("RISC-V division semantics never trap"),
+        }
+    }
+
+    /// Evaluates this operation, resolving division edge cases with `policy`.
+    ///
+    /// Behaves exactly like [`Arith::eval`] except for [`Arith::Div`] and
+    /// [`Arith::Rem`] when the divisor is zero or when a signed division
+    /// overflows (`MIN / -1`). Those cases are handled as described on
+    /// [`DivPolicy`], and a [`Trap`] is returned if the policy faults.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Arith, DivPolicy, Sign, Trap};
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    /// let div = Arith::Div(Sign::Signed);
+    ///
+    /// assert!(div.try_eval(&c(7), &c(0), byte, DivPolicy::X86) == Err(Trap::DivideByZero));
+    /// assert!(div.try_eval(&c(7), &c(0), byte, DivPolicy::RiscV) == Ok(c(0xff)));
+    /// assert!(div.try_eval(&c(7), &c(0), byte, DivPolicy::AArch64) == Ok(c(0)));
+    ///
+    /// assert!(div.try_eval(&c(0x80), &c(0xff), byte, DivPolicy::X86) == Err(Trap::Overflow));
+    /// assert!(div.try_eval(&c(0x80), &c(0xff), byte, DivPolicy::AArch64) == Ok(c(0x80)));
+    /// ```
+    pub fn try_eval(
+        self,
+        lhs: &Constant,
+        rhs: &Constant,
+        bitness: Bitness,
+        policy: DivPolicy,
+    ) -> Result<Constant, Trap> {
+        let bits = bitness.bits();
+        let a = lhs.masked(bitness);
+        let b = rhs.masked(bitness);
+        let amount = (b.data[0] & (bits as u64 - 1)) as u32;
+        Ok(match self {
+            Arith::Add => a.wrapping_add(b),
+            Arith::Sub => a.wrapping_sub(b),
+            Arith::Mul => a.wrapping_mul(b),
+            Arith::Div(sign) => div_rem(a, b, bitness, sign, policy)?.0,
+            Arith::Rem(sign) => div_rem(a, b, bitness, sign, policy)?.1,
+            Arith::And => a.zip_limbs(b, |a, b| a & b),
+            Arith::Or => a.zip_limbs(b, |a, b| a | b),
+            Arith::Xor => a.zip_limbs(b, |a, b| a ^ b),
+            Arith::Shl => a.shifted_left(amount),
+            Arith::Shr(Sign::Unsigned) => a.shifted_right(amount),
+            Arith::Shr(Sign::Signed) => {
+                let shifted = a.sign_extended(bitness).shifted_right(amount);
+                match bits == 512 && a.bit(511) {
+                    // There are no bits above a 512-bit value to carry the
+                    // sign in, so fill the vacated ones directly.
+                    true => shifted
+                        .zip_limbs(Constant::MAX.shifted_right(amount).inverted(), |a, b| a | b),
+                    false => shifted,
+                }
+            }
+            Arith::Rotl(_) => a
+                .shifted_left(amount)
+                .zip_limbs(a.shifted_right((bits - amount) % bits), |a, b| a | b),
+            Arith::Rotr(_) => a
+                .shifted_right(amount)
+                .zip_limbs(a.shifted_left((bits - amount) % bits), |a, b| a | b),
+        }
+        .masked(bitness))
+    }
+
+    /// Returns the bitness of the result of this operation on operands of
+    /// width `bitness`.
+    ///
+    /// This is `bitness` itself for every operation except [`Arith::Mul`],
+    /// whose full product is twice as wide. Returns `None` if that product
+    /// would not fit in a [`Constant`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Arith;
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// let qword = Bitness { log2: 6 };
+    /// assert!(Arith::Add.result_bitness(qword) == Some(qword));
+    /// assert!(Arith::Mul.result_bitness(qword) == Some(Bitness { log2: 7 }));
+    /// assert!(Arith::Mul.result_bitness(Bitness { log2: 9 }).is_none());
+    /// ```
+    pub fn result_bitness(self, bitness: Bitness) -> Option<Bitness> {
+        match self {
+            Arith::Mul => bitness.widened(),
+            _ => Some(bitness),
+        }
+    }
+
+    /// Evaluates this operation and computes the condition flags it sets.
+    ///
+    /// Supports [`Arith::Add`], [`Arith::Sub`], the logical operations and
+    /// the shifts; returns `None` for every other operation. The result is the
+    /// same as [`Arith::eval`], and the flags follow x86 conventions:
+    ///
+    /// - `cf` is the carry out of an addition and the *borrow* out of a
+    ///   subtraction (AArch64 and ARM set `C` to its complement), or the last
+    ///   bit shifted out. Logical operations clear it.
+    /// - `of` is signed overflow for additions and subtractions. For shifts it
+    ///   is computed as x86 does for a one-bit shift, at any nonzero amount.
+    ///   Logical operations clear it.
+    /// - `af` is the carry or borrow out of bit 3, and is clear for shifts and
+    ///   logical operations.
+    ///
+    /// A shift by zero (after taking the amount modulo the width) clears `cf`
+    /// and `of`, whereas x86 leaves all flags untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Arith;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    ///
+    /// let (r, f) = Arith::Add.eval_flags(&c(0x7f), &c(1), byte).unwrap();
+    /// assert!(r == c(0x80));
+    /// assert!(!f.cf && f.of && f.sf && !f.zf && f.af && !f.pf);
+    ///
+    /// let (r, f) = Arith::Sub.eval_flags(&c(1), &c(2), byte).unwrap();
+    /// assert!(r == c(0xff));
+    /// assert!(f.cf && !f.of && f.sf && f.pf);
+    /// ```
+    pub fn eval_flags(
+        self,
+        lhs: &Constant,
+        rhs: &Constant,
+        bitness: Bitness,
+    ) -> Option<(Constant, Flags)> {
+        let top = bitness.bits() - 1;
+        let a = lhs.masked(bitness);
+        let b = rhs.masked(bitness);
+        let r = self.eval(&a, &b, bitness);
+        let amount = (b.data[0] & top as u64) as u32;
+        let (cf, of, af) = match self {
+            Arith::Add | Arith::Sub => {
+                let (sa, sb, sr) = (a.bit(top), b.bit(top), r.bit(top));
+                let (cf, of) = if self == Arith::Add {
+                    (r < a, sa == sb && sr != sa)
+                } else {
+                    (a < b, sa != sb && sr != sa)
+                };
+                (cf, of, (a.data[0] ^ b.data[0] ^ r.data[0]) & 0x10 != 0)
+            }
+            Arith::And | Arith::Or | Arith::Xor => (false, false, false),
+            Arith::Shl | Arith::Shr(_) if amount == 0 => (false, false, false),
+            Arith::Shl => {
+                let cf = a.bit(top + 1 - amount);
+                (cf, r.bit(top) != cf, false)
+            }
+            Arith::Shr(sign) => (
+                a.bit(amount - 1),
+                sign == Sign::Unsigned && a.bit(top),
+                false,
+            ),
+            _ => return None,
+        };
+        Some((
+            r,
+            Flags {
+                cf,
+                of,
+                zf: r.is_zero(),
+                sf: r.bit(top),
+                pf: (r.data[0] as u8).count_ones() & 1 == 0,
+                af,
+            },
+        ))
+    }
+}
+/// Divides two operands already truncated to `bitness`, returning the
+/// quotient and remainder with edge cases resolved by `policy`.
+fn div_rem(
+    a: Constant,
+    b: Constant,
+    bitness: Bitness,
+    sign: Sign,
+    policy: DivPolicy,
+) -> Result<(Constant, Constant), Trap> {
+    let top = bitness.bits() - 1;
+    let (a_neg, b_neg) = match sign {
+        Sign::Unsigned => (false, false),
+        Sign::Signed => (a.bit(top), b.bit(top)),
+    };
+    let abs = |v: Constant, neg: bool| {
+        if neg {
+            v.wrapping_neg().masked(bitness)
+        } else {
+            v
+        }
+    };
+    let Some((q, r)) = abs(a, a_neg).div_rem(abs(b, b_neg)) else {
+        return match policy {
+            DivPolicy::X86 => Err(Trap::DivideByZero),
+            DivPolicy::RiscV => Ok((Constant::ZERO.inverted(), a)),
+            DivPolicy::AArch64 => Ok((Constant::ZERO, a)),
+        };
+    };
+    // Only `MIN / -1` divides two negative numbers into a negative quotient.
+    if policy == DivPolicy::X86 && a_neg && b_neg && q.bit(top) {
+        return Err(Trap::Overflow);
+    }
+    Ok((abs(q, a_neg != b_neg), abs(r, a_neg)))
+}
+/// How division edge cases are resolved by [`Arith::try_eval`].
+///
+/// Each policy mirrors the behavior of a real instruction set so that
+/// rewriters can state which semantics they preserve. The two edge cases are
+/// division by zero and signed overflow (`MIN / -1`).
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::ops::DivPolicy;
+///
+/// let faulting = DivPolicy::X86;
+/// let total = DivPolicy::RiscV;
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
+#[cfg_attr(feature = "exhaust", derive(exhaust::Exhaust))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum DivPolicy {
+    /// Both edge cases raise `#DE`, reported as a [`Trap`]
+    X86,
+    /// Division by zero yields all ones and the remainder yields the dividend;
+    /// overflow yields `MIN` with a remainder of zero
+    RiscV,
+    /// Division by zero yields zero and the remainder (computed with `MSUB`)
+    /// yields the dividend; overflow yields `MIN` with a remainder of zero
+    AArch64,
+}
+/// A fault raised while evaluating an operation.
+///
+/// Returned by [`Arith::try_eval`] when the selected [`DivPolicy`] faults.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::ops::Trap;
+///
+/// let trap = Trap::DivideByZero;
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
+#[cfg_attr(feature = "exhaust", derive(exhaust::Exhaust))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum Trap {
+    /// The divisor was zero
+    DivideByZero,
+    /// A signed division of `MIN` by `-1` overflowed
+    Overflow,
+}
+/// Condition flags produced by [`Arith::eval_flags`].
+///
+/// Field names follow x86; see [`Arith::eval_flags`] for how each flag is
+/// computed and [`Cmp::eval_flags`] for deriving comparisons from them.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::ops::Flags;
+///
+/// let flags = Flags {
+///     zf: true,
+///     pf: true,
+///     ..Flags::default()
+/// };
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Flags {
+    /// Carry flag: unsigned carry or borrow, or the last bit shifted out
+    pub cf: bool,
+    /// Overflow flag: signed overflow
+    pub of: bool,
+    /// Zero flag: the result is zero
+    pub zf: bool,
+    /// Sign flag: the most significant bit of the result
+    pub sf: bool,
+    /// Parity flag: the low byte of the result has an even number of set bits
+    pub pf: bool,
+    /// Auxiliary carry flag: carry or borrow out of bit 3
+    pub af: bool,
+}
 /// The signedness of a numeric value or operation.
 ///
 /// Determines whether a value should be interpreted as signed or unsigned,
@@ -102,6 +439,14 @@ pub enum Endian {
     /// Big-endian: most significant byte first
     Big,
 }
+impl Endian {
+    /// The byte order of the host this crate was compiled for.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endian = Endian::Little;
+    /// The byte order of the host this crate was compiled for.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endian = Endian::Big;
+}
 /// A method of extending values to larger bit widths.
 ///
 /// Specifies how a value should be extended when converting to a larger
@@ -159,3 +504,141 @@ pub enum Cmp {
     /// Not equal to (≠)
     Ne,
 }
+impl Cmp {
+    /// Evaluates this comparison on two constants at the given bit width.
+    ///
+    /// Both operands are truncated to `bitness` and interpreted according to
+    /// the comparison's [`Sign`], if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Cmp, Sign};
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    ///
+    /// assert!(Cmp::Lt(Sign::Signed).eval(&c(0xff), &c(1), byte));
+    /// assert!(!Cmp::Lt(Sign::Unsigned).eval(&c(0xff), &c(1), byte));
+    /// assert!(Cmp::Eq.eval(&c(0x1ff), &c(0xff), byte));
+    /// ```
+    pub fn eval(self, lhs: &Constant, rhs: &Constant, bitness: Bitness) -> bool {
+        let top = bitness.bits() - 1;
+        let a = lhs.masked(bitness);
+        let b = rhs.masked(bitness);
+        let ord = match (self.sign(), a.bit(top), b.bit(top)) {
+            (Some(Sign::Signed), true, false) => Ordering::Less,
+            (Some(Sign::Signed), false, true) => Ordering::Greater,
+            _ => a.cmp(&b),
+        };
+        match self {
+            Cmp::Eq => ord.is_eq(),
+            Cmp::Ne => ord.is_ne(),
+            Cmp::Lt(_) => ord.is_lt(),
+            Cmp::Le(_) => ord.is_le(),
+            Cmp::Gt(_) => ord.is_gt(),
+            Cmp::Ge(_) => ord.is_ge(),
+        }
+    }
+
+    /// Returns the comparison that holds exactly when this one does not.
+    ///
+    /// Used to invert a conditional branch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Cmp, Sign};
+    ///
+    /// assert_eq!(Cmp::Lt(Sign::Signed).negate(), Cmp::Ge(Sign::Signed));
+    /// assert_eq!(Cmp::Eq.negate(), Cmp::Ne);
+    /// ```
+    pub fn negate(self) -> Self {
+        match self {
+            Cmp::Le(sign) => Cmp::Gt(sign),
+            Cmp::Lt(sign) => Cmp::Ge(sign),
+            Cmp::Eq => Cmp::Ne,
+            Cmp::Gt(sign) => Cmp::Le(sign),
+            Cmp::Ge(sign) => Cmp::Lt(sign),
+            Cmp::Ne => Cmp::Eq,
+        }
+    }
+
+    /// Returns the comparison that gives the same result with the operands
+    /// exchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Cmp, Sign};
+    ///
+    /// // a < b is equivalent to b > a
+    /// assert_eq!(Cmp::Lt(Sign::Unsigned).swap(), Cmp::Gt(Sign::Unsigned));
+    /// assert_eq!(Cmp::Ne.swap(), Cmp::Ne);
+    /// ```
+    pub fn swap(self) -> Self {
+        match self {
+            Cmp::Le(sign) => Cmp::Ge(sign),
+            Cmp::Lt(sign) => Cmp::Gt(sign),
+            Cmp::Eq => Cmp::Eq,
+            Cmp::Gt(sign) => Cmp::Lt(sign),
+            Cmp::Ge(sign) => Cmp::Le(sign),
+            Cmp::Ne => Cmp::Ne,
+        }
+    }
+
+    /// Returns the signedness of this comparison, or `None` for equality
+    /// comparisons, which do not depend on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Cmp, Sign};
+    ///
+    /// assert_eq!(Cmp::Ge(Sign::Signed).sign(), Some(Sign::Signed));
+    /// assert_eq!(Cmp::Eq.sign(), None);
+    /// ```
+    pub fn sign(self) -> Option<Sign> {
+        match self {
+            Cmp::Le(sign) | Cmp::Lt(sign) | Cmp::Gt(sign) | Cmp::Ge(sign) => Some(sign),
+            Cmp::Eq | Cmp::Ne => None,
+        }
+    }
+
+    /// Evaluates this comparison from the flags set by comparing two values.
+    ///
+    /// `flags` must come from [`Arith::Sub`] of the left-hand side and the
+    /// right-hand side, as with x86 `CMP`. Unsigned comparisons use `cf` and
+    /// `zf`, while signed comparisons use `sf != of` as "less than".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::{Arith, Cmp, Sign};
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    ///
+    /// // -1 < 1 when signed, but 0xff > 1 when unsigned
+    /// let (_, f) = Arith::Sub.eval_flags(&c(0xff), &c(1), byte).unwrap();
+    /// assert!(Cmp::Lt(Sign::Signed).eval_flags(&f));
+    /// assert!(Cmp::Gt(Sign::Unsigned).eval_flags(&f));
+    /// assert!(Cmp::Ne.eval_flags(&f));
+    /// ```
+    pub fn eval_flags(self, flags: &Flags) -> bool {
+        let lt = |sign| match sign {
+            Sign::Unsigned => flags.cf,
+            Sign::Signed => flags.sf != flags.of,
+        };
+        match self {
+            Cmp::Eq => flags.zf,
+            Cmp::Ne => !flags.zf,
+            Cmp::Lt(sign) => lt(sign),
+            Cmp::Ge(sign) => !lt(sign),
+            Cmp::Le(sign) => lt(sign) || flags.zf,
+            Cmp::Gt(sign) => !lt(sign) && !flags.zf,
+        }
+    }
+}
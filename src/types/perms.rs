@@ -5,6 +5,10 @@
 //! associated permission bits.
 
 use super::*;
+use bitvec::array::BitArray;
+use core::ops::{Bound, RangeBounds};
+use embedded_io::ReadExactError;
+use no_error_type::NoError;
 
 /// A single permission type.
 ///
@@ -238,6 +242,157 @@ impl<'a, T: InputStream> InputStream for &'a mut T {
         (&mut **self).write_all(i)
     }
 }
+/// A trait for types that produce input code with permissions.
+///
+/// The read half of [`InputStream`], modeled on [`embedded_io::Read`].
+/// Implementors fill caller-provided buffers, so large inputs can be streamed
+/// without holding them in memory.
+///
+/// A call reads at most as many bytes as the shortest of the five buffers
+/// holds, and returns `Ok(0)` at end of input unless that length is zero.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::perms::{InputRef, InputSource, Perms};
+/// use bitvec::prelude::*;
+///
+/// let code = &[1, 2, 3][..];
+/// let bits = bits![1, 0, 1];
+/// let mut source = InputRef::new(code, Perms { r: bits, w: bits, x: bits, nj: bits }).unwrap();
+///
+/// let mut buf = [0u8; 2];
+/// let mut r = bitarr![0; 2];
+/// let mut w = bitarr![0; 2];
+/// let mut x = bitarr![0; 2];
+/// let mut nj = bitarr![0; 2];
+/// let perms = Perms { r: &mut r[..2], w: &mut w[..2], x: &mut x[..2], nj: &mut nj[..2] };
+/// assert_eq!(source.read(&mut buf, perms).unwrap(), 2);
+/// assert_eq!(buf, [1, 2]);
+/// assert_eq!(r[..2], bits![1, 0]);
+/// assert_eq!(source.len(), 1);
+/// ```
+pub trait InputSource: ErrorType {
+    /// Reads some input code and permissions into the buffers.
+    ///
+    /// Returns the number of bytes read on success.
+    fn read(&mut self, code: &mut [u8], perms: Perms<&mut BitSlice>) -> Result<usize, Self::Error>;
+
+    /// Reads exactly enough input to fill the buffers.
+    ///
+    /// Repeatedly calls `read` until the shortest buffer is full, failing with
+    /// [`ReadExactError::UnexpectedEof`] if the input ends first.
+    fn read_exact(
+        &mut self,
+        mut code: &mut [u8],
+        mut perms: Perms<&mut BitSlice>,
+    ) -> Result<(), ReadExactError<Self::Error>> {
+        while buf_len(code, perms.as_ref().map(|b| &**b)) > 0 {
+            match self.read(code, perms.as_mut().map(|b| &mut **b))? {
+                0 => return Err(ReadExactError::UnexpectedEof),
+                n => {
+                    code = &mut core::mem::take(&mut code)[n..];
+                    perms = perms.map(|b| &mut b[n..]);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl<T: InputSource> InputSource for &mut T {
+    fn read(&mut self, code: &mut [u8], perms: Perms<&mut BitSlice>) -> Result<usize, Self::Error> {
+        (**self).read(code, perms)
+    }
+    fn read_exact(
+        &mut self,
+        code: &mut [u8],
+        perms: Perms<&mut BitSlice>,
+    ) -> Result<(), ReadExactError<Self::Error>> {
+        (**self).read_exact(code, perms)
+    }
+}
+/// Returns how many bytes fit in all of the buffers.
+fn buf_len(code: &[u8], perms: Perms<&BitSlice>) -> usize {
+    [perms.r, perms.w, perms.x, perms.nj]
+        .iter()
+        .map(|b| b.len())
+        .fold(code.len(), usize::min)
+}
+/// Copies the whole of `source` into `stream`.
+///
+/// Data passes through a fixed buffer on the stack, so no allocation is
+/// needed. Returns the number of bytes copied, or the error of whichever side
+/// failed.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use portal_pc_asm_common::types::perms::{copy, Input, InputRef, Perms};
+/// use bitvec::prelude::*;
+///
+/// let code = vec![0x90; 10000];
+/// let bits = BitVec::repeat(true, code.len());
+/// let perms = Perms { r: &*bits, w: &*bits, x: &*bits, nj: &*bits };
+///
+/// let mut source = InputRef::new(&code, perms).unwrap();
+/// let empty = Perms { r: bitvec![], w: bitvec![], x: bitvec![], nj: bitvec![] };
+/// let mut sink = Input::new(vec![], empty).unwrap();
+/// assert_eq!(copy(&mut source, &mut sink).unwrap(), 10000);
+/// assert!(sink.as_ref() == InputRef::new(&code, perms).unwrap());
+/// # }
+/// ```
+pub fn copy<S: InputSource + ?Sized, T: InputStream + ?Sized>(
+    source: &mut S,
+    stream: &mut T,
+) -> Result<u64, Either<S::Error, T::Error>> {
+    const LEN: usize = 4096;
+    let mut code = [0u8; LEN];
+    let mut bits: Perms<BitArray<[usize; LEN / usize::BITS as usize]>> = Perms {
+        r: BitArray::ZERO,
+        w: BitArray::ZERO,
+        x: BitArray::ZERO,
+        nj: BitArray::ZERO,
+    };
+    let mut total = 0;
+    loop {
+        let n = source
+            .read(&mut code, bits.as_mut().map(|b| b.as_mut_bitslice()))
+            .map_err(Either::Left)?;
+        if n == 0 {
+            return Ok(total);
+        }
+        let perms = bits.as_ref().map(|b| &b[..n]);
+        let input = InputRef {
+            code: &code[..n],
+            r: perms.r,
+            w: perms.w,
+            x: perms.x,
+            nj: perms.nj,
+            attest_same_size: (),
+        };
+        stream.write_all(input).map_err(Either::Right)?;
+        total += n as u64;
+    }
+}
+impl ErrorType for InputRef<'_> {
+    type Error = NoError;
+}
+impl InputSource for InputRef<'_> {
+    /// Reads from the front of the input, advancing past the bytes read.
+    fn read(&mut self, code: &mut [u8], perms: Perms<&mut BitSlice>) -> Result<usize, Self::Error> {
+        let n = buf_len(code, perms.as_ref().map(|b| &**b)).min(self.len());
+        let head = self.subref(..n);
+        code[..n].copy_from_slice(head.code);
+        perms.r[..n].copy_from_bitslice(head.r);
+        perms.w[..n].copy_from_bitslice(head.w);
+        perms.x[..n].copy_from_bitslice(head.x);
+        perms.nj[..n].copy_from_bitslice(head.nj);
+        *self = self.subref(n..);
+        Ok(n)
+    }
+}
 impl<'a> Index<Perm> for InputRef<'a> {
     type Output = &'a BitSlice;
     fn index(&self, index: Perm) -> &Self::Output {
@@ -294,6 +449,16 @@ impl<'a> InputRef<'a> {
         }
     }
 
+    /// Returns the four permission bit slices.
+    pub fn perms(&self) -> Perms<&'a BitSlice> {
+        Perms {
+            r: self.r,
+            w: self.w,
+            x: self.x,
+            nj: self.nj,
+        }
+    }
+
     /// Creates a nested reference with a shorter lifetime.
     pub fn nest<'b>(&'b self) -> InputRef<'b> {
         InputRef {
@@ -376,8 +541,8 @@ impl<'a> InputRef<'a> {
 /// # }
 /// ```
 #[cfg(feature = "alloc")]
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct Input {
     code: alloc::vec::Vec<u8>,
     r: bitvec::vec::BitVec,
@@ -385,12 +550,15 @@ pub struct Input {
     x: bitvec::vec::BitVec,
     nj: bitvec::vec::BitVec,
     attest_same_size: (),
+    /// The number of bytes at the front already consumed through
+    /// [`InputSource`], which every view skips until they are compacted away
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: usize,
 }
 #[cfg(feature = "alloc")]
 const _: () = {
     use alloc::{borrow::ToOwned, vec::Vec};
     use bitvec::vec::BitVec;
-    use no_error_type::NoError;
     impl<'a> InputRef<'a> {
         pub fn to_owned(&self) -> Input {
             Input {
@@ -400,18 +568,20 @@ const _: () = {
                 x: self.x.to_owned(),
                 nj: self.nj.to_owned(),
                 attest_same_size: (),
+                read: 0,
             }
         }
     }
     impl Input {
         pub fn as_ref<'a>(&'a self) -> InputRef<'a> {
+            let n = self.read;
             InputRef {
-                code: &self.code,
-                r: self.r.as_bitslice(),
-                w: self.w.as_bitslice(),
-                x: self.x.as_bitslice(),
-                nj: self.nj.as_bitslice(),
-                attest_same_size: (),
+                code: &self.code[n..],
+                r: &self.r[n..],
+                w: &self.w[n..],
+                x: &self.x[n..],
+                nj: &self.nj[n..],
+                attest_same_size: self.attest_same_size,
             }
         }
         pub fn new(code: Vec<u8>, perms: Perms<BitVec>) -> Option<Self> {
@@ -430,16 +600,19 @@ const _: () = {
                 x,
                 nj,
                 attest_same_size,
+                read: 0,
             })
         }
         pub fn len(&self) -> usize {
-            return self.code.len();
+            return self.code.len() - self.read;
         }
-        pub fn into_parts(self) -> (Vec<u8>, BitVec, BitVec, BitVec, BitVec) {
+        pub fn into_parts(mut self) -> (Vec<u8>, BitVec, BitVec, BitVec, BitVec) {
+            self.compact();
             (self.code, self.r, self.w, self.x, self.nj)
         }
         #[cfg(feature = "enum-map")]
-        pub fn into_mapped_parts(self) -> (Vec<u8>, enum_map::EnumMap<Perm, BitVec>) {
+        pub fn into_mapped_parts(mut self) -> (Vec<u8>, enum_map::EnumMap<Perm, BitVec>) {
+            self.compact();
             (
                 self.code,
                 enum_map::enum_map! {Perm::Read => self.r.clone(),Perm::Write => self.w.clone(),Perm::Exec => self.x.clone(),Perm::NoJump => self.nj.clone()},
@@ -466,9 +639,69 @@ const _: () = {
             self.extend(i.map(|(c, p)| (c, p.into())));
         }
     }
+    impl Input {
+        /// Drops the bytes already read from the storage.
+        fn compact(&mut self) {
+            let n = core::mem::take(&mut self.read);
+            self.code.drain(..n);
+            for b in [&mut self.r, &mut self.w, &mut self.x, &mut self.nj] {
+                b.drain(..n);
+            }
+        }
+    }
+    impl PartialEq for Input {
+        fn eq(&self, other: &Self) -> bool {
+            self.as_ref() == other.as_ref()
+        }
+    }
+    impl Eq for Input {}
+    impl PartialOrd for Input {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Input {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.as_ref().cmp(&other.as_ref())
+        }
+    }
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for Input {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+            let i = self.as_ref();
+            let mut s = serializer.serialize_struct("Input", 6)?;
+            s.serialize_field("code", i.code)?;
+            s.serialize_field("r", i.r)?;
+            s.serialize_field("w", i.w)?;
+            s.serialize_field("x", i.x)?;
+            s.serialize_field("nj", i.nj)?;
+            s.serialize_field("attest_same_size", &())?;
+            s.end()
+        }
+    }
     impl ErrorType for Input {
         type Error = NoError;
     }
+    impl InputSource for Input {
+        /// Removes the bytes read from the front of the input, so that an
+        /// `Input` used as both [`InputStream`] and `InputSource` acts as a
+        /// queue. The storage of read bytes is reclaimed once they make up
+        /// more than half of it, so reading a queue costs time linear in the
+        /// bytes read.
+        fn read(
+            &mut self,
+            code: &mut [u8],
+            perms: Perms<&mut BitSlice>,
+        ) -> Result<usize, Self::Error> {
+            let n = self.as_ref().read(code, perms)?;
+            self.read += n;
+            if self.read > self.code.len() / 2 {
+                self.compact();
+            }
+            Ok(n)
+        }
+    }
     impl InputStream for Input {
         fn write(&mut self, i: InputRef<'_>) -> Result<usize, Self::Error> {
             self.code.extend_from_slice(i.code);
@@ -484,3 +717,568 @@ const _: () = {
         }
     }
 };
+/// Run-length permissions for a range of bytes.
+///
+/// Stores sorted, maximal runs of identical [`Perms<bool>`] instead of four
+/// bits per byte, so whole pages or segments sharing permissions cost a
+/// single entry. Point queries are `O(log n)` in the number of runs.
+/// Available only with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use portal_pc_asm_common::types::perms::{PermMap, Perms};
+///
+/// let none = Perms { r: false, w: false, x: false, nj: true };
+/// let text = Perms { r: true, w: false, x: true, nj: false };
+///
+/// let mut map = PermMap::new(0x3000, none);
+/// map.set(0x1000..0x2000, text);
+/// assert!(map.get(0x1800) == Some(text));
+/// assert!(map.get(0x2000) == Some(none));
+/// assert_eq!(map.runs().count(), 3);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PermMap {
+    len: usize,
+    /// Run starts in increasing order; the first is `0` unless empty, and
+    /// neighbouring runs always differ.
+    runs: alloc::vec::Vec<(usize, Perms<bool>)>,
+}
+#[cfg(feature = "alloc")]
+const _: () = {
+    use alloc::vec::Vec;
+    use bitvec::vec::BitVec;
+    use core::ops::Range;
+    impl PermMap {
+        /// Creates a map of `len` bytes which all have `perms`.
+        pub fn new(len: usize, perms: Perms<bool>) -> Self {
+            Self {
+                len,
+                runs: if len == 0 {
+                    Vec::new()
+                } else {
+                    [(0, perms)].into()
+                },
+            }
+        }
+
+        /// Builds a map from per-byte permission bits.
+        ///
+        /// Returns `None` if the slices don't all have the same length.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #[cfg(feature = "alloc")]
+        /// # {
+        /// use portal_pc_asm_common::types::perms::{PermMap, Perms};
+        /// use bitvec::prelude::*;
+        ///
+        /// let bits = Perms {
+        ///     r: bits![1, 1, 1, 1],
+        ///     w: bits![0, 0, 1, 1],
+        ///     x: bits![1, 1, 0, 0],
+        ///     nj: bits![0, 1, 1, 1],
+        /// };
+        /// let map = PermMap::from_bits(bits).unwrap();
+        /// assert_eq!(map.runs().map(|(r, _)| r).collect::<Vec<_>>(), [0..1, 1..2, 2..4]);
+        /// assert!(map.to_bits().as_ref().map(|b| b.as_bitslice()) == bits);
+        /// # }
+        /// ```
+        pub fn from_bits(bits: Perms<&BitSlice>) -> Option<Self> {
+            let len = bits.r.len();
+            if [bits.w, bits.x, bits.nj].iter().any(|b| b.len() != len) {
+                return None;
+            }
+            // Returns the first index past `at` where `b` differs from `b[at]`.
+            let change = |b: &BitSlice, at: usize| {
+                let rest = &b[at..];
+                match b[at] {
+                    true => rest.first_zero(),
+                    false => rest.first_one(),
+                }
+                .map_or(len, |n| at + n)
+            };
+            let slices = [bits.r, bits.w, bits.x, bits.nj];
+            let mut runs = Vec::new();
+            if len == 0 {
+                return Some(Self { len, runs });
+            }
+            // Each slice is rescanned only from its own change points, so every
+            // bit is visited once.
+            let mut next = slices.map(|b| change(b, 0));
+            let mut start = 0;
+            while start < len {
+                let end = next.into_iter().min().unwrap_or(len);
+                runs.push((start, bits.map(|b| b[start])));
+                for (n, b) in next.iter_mut().zip(slices) {
+                    if *n == end && end < len {
+                        *n = change(b, end);
+                    }
+                }
+                start = end;
+            }
+            Some(Self { len, runs })
+        }
+
+        /// Expands the map back into per-byte permission bits.
+        pub fn to_bits(&self) -> Perms<BitVec> {
+            let mut bits = Perms {
+                r: BitVec::repeat(false, self.len),
+                w: BitVec::repeat(false, self.len),
+                x: BitVec::repeat(false, self.len),
+                nj: BitVec::repeat(false, self.len),
+            };
+            for (range, perms) in self.runs() {
+                bits.r[range.clone()].fill(perms.r);
+                bits.w[range.clone()].fill(perms.w);
+                bits.x[range.clone()].fill(perms.x);
+                bits.nj[range].fill(perms.nj);
+            }
+            bits
+        }
+
+        /// Returns the number of bytes covered by the map.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Returns `true` if the map covers no bytes.
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Index into `runs` of the run containing `index`, which must be in bounds.
+        fn run_of(&self, index: usize) -> usize {
+            self.runs.partition_point(|&(start, _)| start <= index) - 1
+        }
+
+        /// Returns the end of run `i`.
+        fn run_end(&self, i: usize) -> usize {
+            self.runs.get(i + 1).map_or(self.len, |&(start, _)| start)
+        }
+
+        /// Returns the permissions of the byte at `index`, or `None` if it is
+        /// out of bounds.
+        pub fn get(&self, index: usize) -> Option<Perms<bool>> {
+            if index >= self.len {
+                return None;
+            }
+            Some(self.runs[self.run_of(index)].1)
+        }
+
+        /// Returns the maximal uniform run containing `index`, or `None` if it
+        /// is out of bounds.
+        pub fn run_at(&self, index: usize) -> Option<(Range<usize>, Perms<bool>)> {
+            if index >= self.len {
+                return None;
+            }
+            let i = self.run_of(index);
+            let (start, perms) = self.runs[i];
+            Some((start..self.run_end(i), perms))
+        }
+
+        /// Sets the permissions of every byte in `range` to `perms`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `range` is out of bounds or decreasing, like slice indexing.
+        pub fn set(&mut self, range: Range<usize>, perms: Perms<bool>) {
+            let Range { start, end } = range;
+            assert!(start <= end, "range starts at {start} but ends at {end}");
+            assert!(
+                end <= self.len,
+                "range end {end} out of bounds for length {}",
+                self.len
+            );
+            if start == end {
+                return;
+            }
+            let after = self.get(end);
+            let lo = self.runs.partition_point(|&(s, _)| s < start);
+            let hi = self.runs.partition_point(|&(s, _)| s <= end);
+            let keep_start = lo == 0 || self.runs[lo - 1].1 != perms;
+            let keep_end = after.is_some_and(|a| a != perms);
+            self.runs.splice(
+                lo..hi,
+                [(start, perms)]
+                    .into_iter()
+                    .filter(|_| keep_start)
+                    .chain(after.map(|a| (end, a)).filter(|_| keep_end)),
+            );
+        }
+
+        /// Replaces the permissions of every byte in `range` with `f` applied
+        /// to their current value.
+        ///
+        /// # Panics
+        ///
+        /// Panics if `range` is out of bounds or decreasing, like slice indexing.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # #[cfg(feature = "alloc")]
+        /// # {
+        /// use portal_pc_asm_common::types::perms::{PermMap, Perms};
+        ///
+        /// let rx = Perms { r: true, w: false, x: true, nj: false };
+        /// let rw = Perms { r: true, w: true, x: false, nj: true };
+        /// let mut map = PermMap::new(16, rx);
+        /// map.set(8..16, rw);
+        ///
+        /// // Revoke write access across both runs.
+        /// map.update(4..12, |p| Perms { w: false, ..p });
+        /// assert!(map.get(10) == Some(Perms { w: false, ..rw }));
+        /// assert_eq!(map.runs().count(), 3);
+        /// # }
+        /// ```
+        pub fn update(
+            &mut self,
+            range: Range<usize>,
+            mut f: impl FnMut(Perms<bool>) -> Perms<bool>,
+        ) {
+            let Range { start, end } = range;
+            assert!(start <= end, "range starts at {start} but ends at {end}");
+            assert!(
+                end <= self.len,
+                "range end {end} out of bounds for length {}",
+                self.len
+            );
+            let mut at = start;
+            while at < end {
+                let i = self.run_of(at);
+                let next = self.run_end(i).min(end);
+                let perms = f(self.runs[i].1);
+                self.set(at..next, perms);
+                at = next;
+            }
+        }
+
+        /// Returns an iterator over the maximal uniform runs, in order.
+        ///
+        /// Each item is the byte range of the run and its permissions;
+        /// neighbouring runs always have different permissions.
+        pub fn runs(&self) -> impl Iterator<Item = (Range<usize>, Perms<bool>)> + '_ {
+            self.runs
+                .iter()
+                .enumerate()
+                .map(|(i, &(start, perms))| (start..self.run_end(i), perms))
+        }
+    }
+    impl<'a> From<InputRef<'a>> for PermMap {
+        fn from(value: InputRef<'a>) -> Self {
+            let Some(map) = Self::from_bits(value.perms()) else {
+                unreachable!("InputRef slices have the same length")
+            };
+            map
+        }
+    }
+};
+/// An [`InputRef`] placed at a virtual base address.
+///
+/// Byte `i` of the input lives at address `base + i`. The whole input must
+/// fit below `u64::MAX`, which is checked at construction time.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::perms::{AddrInputRef, InputRef, Perms};
+/// use bitvec::prelude::*;
+///
+/// let code = &[0x55, 0x48, 0x89, 0xe5][..];
+/// let bits = bits![1, 1, 1, 1];
+/// let input = InputRef::new(code, Perms { r: bits, w: bits, x: bits, nj: bits }).unwrap();
+///
+/// let addressed = AddrInputRef::new(0x401000, input).unwrap();
+/// assert_eq!(addressed.addr_of(2), Some(0x401002));
+/// assert_eq!(addressed.index_of(0x401003), Some(3));
+///
+/// let tail = addressed.subref(0x401001..).unwrap();
+/// assert_eq!(tail.base(), 0x401001);
+/// assert_eq!(tail.input().code, &[0x48, 0x89, 0xe5]);
+/// assert!(addressed.subref(0x400fff..0x401001).is_none());
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AddrInputRef<'a> {
+    base: u64,
+    input: InputRef<'a>,
+}
+impl<'a> AddrInputRef<'a> {
+    /// Places `input` at `base`.
+    ///
+    /// Returns `None` if the input would extend past `u64::MAX`.
+    pub fn new(base: u64, input: InputRef<'a>) -> Option<Self> {
+        base.checked_add(input.len() as u64)?;
+        Some(Self { base, input })
+    }
+
+    /// Returns the address of the first byte.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Returns the address one past the last byte.
+    pub fn end(&self) -> u64 {
+        self.base + self.input.len() as u64
+    }
+
+    /// Returns the underlying position-less input.
+    pub fn input(&self) -> InputRef<'a> {
+        self.input
+    }
+
+    /// Returns the length of the input in bytes.
+    pub fn len(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Returns `true` if the input has no bytes.
+    pub fn is_empty(&self) -> bool {
+        self.input.len() == 0
+    }
+
+    /// Returns `true` if `addr` falls within the input.
+    pub fn contains(&self, addr: u64) -> bool {
+        (self.base..self.end()).contains(&addr)
+    }
+
+    /// Returns the address of the byte at `index`, or `None` if it is out of
+    /// bounds.
+    pub fn addr_of(&self, index: usize) -> Option<u64> {
+        (index < self.len()).then(|| self.base + index as u64)
+    }
+
+    /// Returns the index of the byte at `addr`, or `None` if it is outside
+    /// the input.
+    pub fn index_of(&self, addr: u64) -> Option<usize> {
+        self.contains(addr).then(|| (addr - self.base) as usize)
+    }
+
+    /// Returns the byte at `addr` and its permissions.
+    pub fn get(&self, addr: u64) -> Option<(u8, Perms<bool>)> {
+        let i = self.index_of(addr)?;
+        Some((self.input.code[i], self.input.perms().map(|b| b[i])))
+    }
+
+    /// Creates a sub-reference covering a range of virtual addresses.
+    ///
+    /// Returns `None` unless the range lies within the input.
+    pub fn subref(self, range: impl RangeBounds<u64>) -> Option<Self> {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.checked_add(1)?,
+            Bound::Unbounded => self.base,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1)?,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.end(),
+        };
+        if !(self.base <= start && start <= end && end <= self.end()) {
+            return None;
+        }
+        let i = (start - self.base) as usize;
+        let j = (end - self.base) as usize;
+        Some(Self {
+            base: start,
+            input: self.input.subref(i..j),
+        })
+    }
+
+    /// Creates a nested reference with a shorter lifetime.
+    pub fn nest<'b>(&'b self) -> AddrInputRef<'b> {
+        AddrInputRef {
+            base: self.base,
+            input: self.input.nest(),
+        }
+    }
+}
+/// An owned [`Input`] placed at a virtual base address.
+///
+/// The owned counterpart of [`AddrInputRef`]. Available only with the
+/// `alloc` feature.
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AddrInput {
+    base: u64,
+    input: Input,
+}
+/// Several non-overlapping addressed inputs, kept sorted by address.
+///
+/// Models an address space with holes, such as the loaded segments of a
+/// binary. Lookups by address are `O(log n)` in the number of segments.
+/// Available only with the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// use portal_pc_asm_common::types::perms::{AddrInput, Input, Perms, Segments};
+/// use bitvec::prelude::*;
+///
+/// let segment = |base: u64, code: &[u8], x: bool| {
+///     let n = code.len();
+///     let perms = Perms {
+///         r: BitVec::repeat(true, n),
+///         w: BitVec::repeat(!x, n),
+///         x: BitVec::repeat(x, n),
+///         nj: BitVec::repeat(!x, n),
+///     };
+///     AddrInput::new(base, Input::new(code.to_vec(), perms).unwrap()).unwrap()
+/// };
+///
+/// let mut segments = Segments::new();
+/// assert!(segments.insert(segment(0x1000, &[0xc3], true)).is_none());
+/// assert!(segments.insert(segment(0x3000, &[1, 2, 3, 4], false)).is_none());
+/// // Overlapping segments are handed back.
+/// assert!(segments.insert(segment(0x3002, &[5], false)).is_some());
+///
+/// assert_eq!(segments.get(0x1000).unwrap().0, 0xc3);
+/// assert!(segments.get(0x2000).is_none());
+/// assert_eq!(segments.segment(0x3003).unwrap().base(), 0x3000);
+/// assert_eq!(segments.subref(0x3001..0x3003).unwrap().input().code, &[2, 3]);
+/// assert!(segments.subref(0x1000..0x3001).is_none());
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(PartialEq, Eq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Segments {
+    segments: alloc::vec::Vec<AddrInput>,
+}
+#[cfg(feature = "alloc")]
+const _: () = {
+    use alloc::vec::Vec;
+    use core::ops::Range;
+    impl AddrInput {
+        /// Places `input` at `base`.
+        ///
+        /// Returns `None` if the input would extend past `u64::MAX`.
+        pub fn new(base: u64, input: Input) -> Option<Self> {
+            AddrInputRef::new(base, input.as_ref())?;
+            Some(Self { base, input })
+        }
+        /// Borrows the input as an [`AddrInputRef`].
+        pub fn as_ref<'a>(&'a self) -> AddrInputRef<'a> {
+            AddrInputRef {
+                base: self.base,
+                input: self.input.as_ref(),
+            }
+        }
+        /// Returns the address of the first byte.
+        pub fn base(&self) -> u64 {
+            self.base
+        }
+        /// Returns the address one past the last byte.
+        pub fn end(&self) -> u64 {
+            self.as_ref().end()
+        }
+        /// Returns the length of the input in bytes.
+        pub fn len(&self) -> usize {
+            self.input.len()
+        }
+        /// Returns `true` if the input has no bytes.
+        pub fn is_empty(&self) -> bool {
+            self.input.len() == 0
+        }
+        /// Splits into the base address and the position-less input.
+        pub fn into_parts(self) -> (u64, Input) {
+            (self.base, self.input)
+        }
+    }
+    impl<'a> AddrInputRef<'a> {
+        /// Copies the input into an owned [`AddrInput`].
+        pub fn to_owned(&self) -> AddrInput {
+            AddrInput {
+                base: self.base,
+                input: self.input.to_owned(),
+            }
+        }
+    }
+    impl Segments {
+        /// Creates an empty set of segments.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Adds a segment.
+        ///
+        /// Empty segments cover no addresses and are dropped. Returns the
+        /// segment back, leaving the container unchanged, if it overlaps one
+        /// already present.
+        pub fn insert(&mut self, segment: AddrInput) -> Option<AddrInput> {
+            if segment.is_empty() {
+                return None;
+            }
+            let i = self.segments.partition_point(|s| s.base < segment.base);
+            let fits_before = i == 0 || self.segments[i - 1].end() <= segment.base;
+            let fits_after = self.segments.get(i).is_none_or(|s| segment.end() <= s.base);
+            if !(fits_before && fits_after) {
+                return Some(segment);
+            }
+            self.segments.insert(i, segment);
+            None
+        }
+
+        /// Removes and returns the segment containing `addr`.
+        pub fn remove(&mut self, addr: u64) -> Option<AddrInput> {
+            let i = self.find(addr)?;
+            Some(self.segments.remove(i))
+        }
+
+        /// Returns the number of segments.
+        pub fn len(&self) -> usize {
+            self.segments.len()
+        }
+
+        /// Returns `true` if there are no segments.
+        pub fn is_empty(&self) -> bool {
+            self.segments.is_empty()
+        }
+
+        /// Index of the segment containing `addr`.
+        fn find(&self, addr: u64) -> Option<usize> {
+            let i = self.segments.partition_point(|s| s.base <= addr);
+            let i = i.checked_sub(1)?;
+            (addr < self.segments[i].end()).then_some(i)
+        }
+
+        /// Returns the segment containing `addr`.
+        pub fn segment(&self, addr: u64) -> Option<AddrInputRef<'_>> {
+            Some(self.segments[self.find(addr)?].as_ref())
+        }
+
+        /// Returns the byte at `addr` and its permissions.
+        pub fn get(&self, addr: u64) -> Option<(u8, Perms<bool>)> {
+            self.segment(addr)?.get(addr)
+        }
+
+        /// Creates a sub-reference covering a range of virtual addresses.
+        ///
+        /// Returns `None` unless the whole range lies within a single segment.
+        /// An empty range must start inside a segment.
+        pub fn subref(&self, range: Range<u64>) -> Option<AddrInputRef<'_>> {
+            self.segment(range.start)?.subref(range)
+        }
+
+        /// Returns an iterator over the segments in address order.
+        pub fn iter(&self) -> impl Iterator<Item = AddrInputRef<'_>> + '_ {
+            self.segments.iter().map(AddrInput::as_ref)
+        }
+
+        /// Consumes the container, returning the segments in address order.
+        pub fn into_vec(self) -> Vec<AddrInput> {
+            self.segments
+        }
+    }
+};
@@ -3,7 +3,19 @@
 //! This module provides types for representing CPU registers in a
 //! platform-independent manner.
 
+use super::ops::Ext;
+use super::value::{Bitness, ParseError, Value};
 use super::*;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Range, Sub, SubAssign,
+};
+use core::str::FromStr;
+
+pub mod aarch64;
+pub mod mips;
+pub mod riscv;
+pub mod x86_64;
 
 /// A register identifier.
 ///
@@ -109,3 +121,737 @@ impl Reg {
         return Self(self.0 % 32);
     }
 }
+/// Formats as `r` followed by the register number, such as `r5`, or as `ctx`
+/// for [`Reg::CTX`].
+impl Display for Reg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if *self == Reg::CTX {
+            f.write_str("ctx")
+        } else {
+            write!(f, "r{}", self.0)
+        }
+    }
+}
+impl FromStr for Reg {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "ctx" {
+            return Ok(Reg::CTX);
+        }
+        s.strip_prefix('r')
+            .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|d| d.parse().ok())
+            .map(Reg)
+            .ok_or(ParseError::Offset)
+    }
+}
+/// The role of an architectural register.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::reg::{x86_64, RegClass};
+///
+/// assert_eq!(x86_64::REGS.by_name("rsp").unwrap().class, RegClass::StackPointer);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
+#[cfg_attr(feature = "exhaust", derive(exhaust::Exhaust))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum RegClass {
+    /// A general-purpose register
+    General,
+    /// The stack pointer
+    StackPointer,
+    /// The conventional frame pointer
+    FramePointer,
+    /// The register receiving return addresses
+    Link,
+    /// A register hardwired to zero
+    Zero,
+}
+/// An architectural name for all or part of a [`Reg`].
+///
+/// Sub-registers such as `eax` share the [`Reg`] of their full register
+/// (`rax`) with a narrower [`Bitness`], and `bit_offset` locates high-byte
+/// registers such as `ah`.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::reg::{x86_64, Reg};
+/// use portal_pc_asm_common::types::value::Bitness;
+///
+/// let ah = x86_64::REGS.by_name("ah").unwrap();
+/// assert_eq!(ah.value.offset, Reg(0));
+/// assert_eq!(ah.value.bitness, Bitness::I8);
+/// assert_eq!(ah.bit_offset, 8);
+/// assert_eq!(ah.encoding, 4);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct RegName {
+    /// The assembler name, in lowercase and without a sigil
+    pub name: &'static str,
+    /// The full register and the width this name accesses
+    pub value: Value<Reg>,
+    /// The bit offset of the accessed bits within the full register
+    pub bit_offset: u32,
+    /// The number used for this name in instruction encodings
+    pub encoding: u8,
+    /// The role of the register
+    pub class: RegClass,
+}
+impl RegName {
+    /// Creates a name for the low `bitness` bits of `reg`.
+    pub const fn new(
+        name: &'static str,
+        reg: Reg,
+        bitness: Bitness,
+        encoding: u8,
+        class: RegClass,
+    ) -> Self {
+        RegName {
+            name,
+            value: Value {
+                offset: reg,
+                bitness,
+            },
+            bit_offset: 0,
+            encoding,
+            class,
+        }
+    }
+
+    /// Returns `true` if every bit accessed by this name is also accessed by
+    /// `other`, as `eax` is contained in `rax`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap();
+    /// assert!(name("eax").is_within(name("rax")));
+    /// assert!(name("ah").is_within(name("ax")));
+    /// assert!(!name("ah").is_within(name("al")));
+    /// ```
+    pub fn is_within(&self, other: &RegName) -> bool {
+        self.value.offset == other.value.offset
+            && self.bit_offset >= other.bit_offset
+            && self.bit_offset + self.value.bitness.bits()
+                <= other.bit_offset + other.value.bitness.bits()
+    }
+
+    /// Returns `true` if this name and `other` access any common bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap();
+    /// assert!(name("ah").overlaps(name("eax")));
+    /// assert!(!name("ah").overlaps(name("al")));
+    /// ```
+    pub fn overlaps(&self, other: &RegName) -> bool {
+        self.value.offset == other.value.offset
+            && self.bit_offset < other.bit_offset + other.value.bitness.bits()
+            && other.bit_offset < self.bit_offset + self.value.bitness.bits()
+    }
+
+    /// Returns the bits of the full register this name reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap();
+    /// assert_eq!(name("ah").bit_range(), 8..16);
+    /// assert_eq!(name("al").bit_range(), 0..8);
+    /// ```
+    pub fn bit_range(&self) -> Range<u32> {
+        span(&self.value, self.bit_offset)
+    }
+
+    /// Returns the bits of the full register changed by writing this name.
+    ///
+    /// Only writes starting at bit 0 can extend into the whole register;
+    /// writes to high-byte registers such as `ah` leave the other bits alone.
+    pub fn written_bits(&self, map: &RegMap) -> Range<u32> {
+        written(&self.value, self.bit_offset, map)
+    }
+
+    /// Returns `true` if writing this name determines every bit later read
+    /// through `read`, so no earlier definition of `read` is live across the
+    /// write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap();
+    /// let defines = |w, r| name(w).defines(name(r), &x86_64::REGS);
+    /// assert!(!defines("ah", "al"));
+    /// assert!(!defines("al", "ah"));
+    /// assert!(!defines("ah", "ax"));
+    /// assert!(defines("ax", "ah"));
+    /// assert!(defines("eax", "ah"));
+    /// ```
+    pub fn defines(&self, read: &RegName, map: &RegMap) -> bool {
+        defines(
+            &self.value,
+            self.bit_offset,
+            &read.value,
+            read.bit_offset,
+            map,
+        )
+    }
+
+    /// Returns `true` if writing this name also reads the register, because
+    /// bits outside the write keep their previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap();
+    /// assert!(name("ah").write_merges(&x86_64::REGS));
+    /// assert!(!name("eax").write_merges(&x86_64::REGS));
+    /// ```
+    pub fn write_merges(&self, map: &RegMap) -> bool {
+        self.bit_offset != 0 || map.partial_write(self.value.bitness).merges()
+    }
+}
+/// The bits of a register accessed by `value` at `bit_offset`.
+fn span(value: &Value<Reg>, bit_offset: u32) -> Range<u32> {
+    bit_offset..bit_offset + value.bitness.bits()
+}
+/// The bits of a register changed by writing `value` at `bit_offset`.
+fn written(value: &Value<Reg>, bit_offset: u32, map: &RegMap) -> Range<u32> {
+    match map.partial_write(value.bitness) {
+        PartialWrite::Full | PartialWrite::Extend(_) if bit_offset == 0 => {
+            0..map.full.bits().max(value.bitness.bits())
+        }
+        _ => span(value, bit_offset),
+    }
+}
+/// Whether writing `write` at `write_offset` determines all of `read` at
+/// `read_offset`.
+fn defines(
+    write: &Value<Reg>,
+    write_offset: u32,
+    read: &Value<Reg>,
+    read_offset: u32,
+    map: &RegMap,
+) -> bool {
+    let written = written(write, write_offset, map);
+    let read_bits = span(read, read_offset);
+    write.offset == read.offset && written.start <= read_bits.start && read_bits.end <= written.end
+}
+/// A table of the architectural register names of one architecture.
+///
+/// Each architecture module provides one as `REGS`. Several names may refer
+/// to the same bits (such as `x8`, `s0` and `fp` on RISC-V); the first entry
+/// is the canonical name.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct RegMap {
+    /// The width of the full registers
+    pub full: Bitness,
+    /// Every register name of the architecture
+    pub names: &'static [RegName],
+    /// The narrower write widths that also overwrite the upper bits of the
+    /// register, and how they fill them; writes of any other narrower width
+    /// leave the upper bits unchanged
+    pub extending_writes: &'static [(Bitness, Ext)],
+}
+impl RegMap {
+    /// Returns what a write of `bitness` bits does to the rest of a full
+    /// register.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Ext;
+    /// use portal_pc_asm_common::types::reg::{riscv, x86_64, PartialWrite};
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(x86_64::REGS.partial_write(Bitness::I32), PartialWrite::Extend(Ext::Zero));
+    /// assert_eq!(x86_64::REGS.partial_write(Bitness::I16), PartialWrite::Preserve);
+    /// assert_eq!(x86_64::REGS.partial_write(Bitness::I64), PartialWrite::Full);
+    /// assert_eq!(riscv::RV64.partial_write(Bitness::I32), PartialWrite::Extend(Ext::Sign));
+    /// ```
+    pub fn partial_write(&self, bitness: Bitness) -> PartialWrite {
+        if bitness >= self.full {
+            return PartialWrite::Full;
+        }
+        match self.extending_writes.iter().find(|(b, _)| *b == bitness) {
+            Some((_, ext)) => PartialWrite::Extend(*ext),
+            None => PartialWrite::Preserve,
+        }
+    }
+
+    /// Looks up a register by name.
+    ///
+    /// Matching is case-insensitive and ignores a leading `%` or `$` sigil, so
+    /// AT&T and MIPS syntax are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::{mips, x86_64, Reg};
+    ///
+    /// assert_eq!(x86_64::REGS.by_name("%R9D").unwrap().value.offset, Reg(9));
+    /// assert_eq!(mips::MIPS64.by_name("$ra").unwrap().value.offset, Reg(31));
+    /// ```
+    pub fn by_name(&self, name: &str) -> Option<&'static RegName> {
+        let name = name.strip_prefix(['%', '$']).unwrap_or(name);
+        self.names
+            .iter()
+            .find(|n| n.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the canonical name for the low `bitness` bits of `reg`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::{aarch64, x86_64, Reg};
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(x86_64::REGS.name_of(Reg(3), Bitness::I16).unwrap().name, "bx");
+    /// assert_eq!(aarch64::REGS.name_of(aarch64::SP, Bitness::I32).unwrap().name, "wsp");
+    /// ```
+    pub fn name_of(&self, reg: Reg, bitness: Bitness) -> Option<&'static RegName> {
+        self.names
+            .iter()
+            .find(|n| n.value.offset == reg && n.value.bitness == bitness && n.bit_offset == 0)
+    }
+
+    /// Returns the canonical full-width name of `reg`.
+    pub fn full_name_of(&self, reg: Reg) -> Option<&'static RegName> {
+        self.name_of(reg, self.full)
+    }
+
+    /// Returns every name with the given encoding and width.
+    ///
+    /// Several registers can share an encoding, such as `sp` and `xzr` on
+    /// AArch64, which are distinguished by the instruction using them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::{aarch64, RegClass};
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// let mut enc31 = aarch64::REGS.by_encoding(31, Bitness::I64).map(|n| n.class);
+    /// assert_eq!(enc31.next(), Some(RegClass::Zero));
+    /// assert_eq!(enc31.next(), Some(RegClass::StackPointer));
+    /// assert_eq!(enc31.next(), None);
+    /// ```
+    pub fn by_encoding(
+        &self,
+        encoding: u8,
+        bitness: Bitness,
+    ) -> impl Iterator<Item = &'static RegName> + use<> {
+        let names = self.names;
+        names
+            .iter()
+            .enumerate()
+            .filter(move |(i, n)| {
+                n.encoding == encoding
+                    && n.value.bitness == bitness
+                    // Skip aliases of a register already yielded.
+                    && !names[..*i]
+                        .iter()
+                        .any(|m| m.value == n.value && m.bit_offset == n.bit_offset)
+            })
+            .map(|(_, n)| n)
+    }
+}
+/// The effect of writing part of a register on the rest of it.
+///
+/// Returned by [`RegMap::partial_write`].
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
+#[cfg_attr(feature = "exhaust", derive(exhaust::Exhaust))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum PartialWrite {
+    /// The write covers the whole register
+    Full,
+    /// The bits above the write keep their previous value
+    Preserve,
+    /// The bits above the write are filled by extending the written value
+    Extend(Ext),
+}
+impl PartialWrite {
+    /// Returns `true` if the previous contents of the register survive the
+    /// write, so the register is both read and written.
+    pub const fn merges(self) -> bool {
+        matches!(self, PartialWrite::Preserve)
+    }
+}
+/// Sub-register aliasing queries.
+///
+/// A `Value<Reg>` names the low [`Value::bitness`] bits of a full register;
+/// these methods relate such views of the same register to each other.
+///
+/// The [`RegName::value`] of a high-byte register such as `ah` equals that of
+/// `al`, so these methods treat it as the low byte. Use the methods of the
+/// same name on [`RegName`] for queries that honor [`RegName::bit_offset`].
+impl Value<Reg> {
+    /// Returns the bits of the full register this value reads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let ax = x86_64::REGS.by_name("ax").unwrap().value;
+    /// assert_eq!(ax.bit_range(), 0..16);
+    /// ```
+    pub fn bit_range(&self) -> Range<u32> {
+        span(self, 0)
+    }
+
+    /// Returns `true` if this value and `other` share any bits.
+    ///
+    /// Since both views start at bit 0, this holds exactly when they name the
+    /// same register.
+    pub fn overlaps(&self, other: &Value<Reg>) -> bool {
+        self.offset == other.offset
+    }
+
+    /// Returns `true` if every bit of this value is also a bit of `other`.
+    pub fn is_within(&self, other: &Value<Reg>) -> bool {
+        self.offset == other.offset && self.bitness <= other.bitness
+    }
+
+    /// Returns the bits of the full register changed by writing this value.
+    ///
+    /// This is wider than [`Value::bit_range`] when the architecture extends
+    /// writes of this width into the whole register.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap().value;
+    /// assert_eq!(name("eax").written_bits(&x86_64::REGS), 0..64);
+    /// assert_eq!(name("ax").written_bits(&x86_64::REGS), 0..16);
+    /// ```
+    pub fn written_bits(&self, map: &RegMap) -> Range<u32> {
+        written(self, 0, map)
+    }
+
+    /// Returns `true` if writing this value determines every bit later read
+    /// through `read`, so no earlier definition of `read` is live across the
+    /// write.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::{aarch64, x86_64};
+    ///
+    /// let x86 = |n| x86_64::REGS.by_name(n).unwrap().value;
+    /// assert!(x86("eax").defines(&x86("rax"), &x86_64::REGS));
+    /// assert!(!x86("ax").defines(&x86("eax"), &x86_64::REGS));
+    /// assert!(x86("ax").defines(&x86("al"), &x86_64::REGS));
+    /// assert!(!x86("ax").defines(&x86("rbx"), &x86_64::REGS));
+    ///
+    /// let arm = |n| aarch64::REGS.by_name(n).unwrap().value;
+    /// assert!(arm("w3").defines(&arm("x3"), &aarch64::REGS));
+    /// ```
+    pub fn defines(&self, read: &Value<Reg>, map: &RegMap) -> bool {
+        defines(self, 0, read, 0, map)
+    }
+
+    /// Returns `true` if writing this value also reads the register, because
+    /// bits outside the write keep their previous value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::reg::x86_64;
+    ///
+    /// let name = |n| x86_64::REGS.by_name(n).unwrap().value;
+    /// assert!(name("al").write_merges(&x86_64::REGS));
+    /// assert!(!name("eax").write_merges(&x86_64::REGS));
+    /// assert!(!name("rax").write_merges(&x86_64::REGS));
+    /// ```
+    pub fn write_merges(&self, map: &RegMap) -> bool {
+        map.partial_write(self.bitness).merges()
+    }
+}
+/// A set of registers, stored as a 256-bit bitset indexed by [`Reg`].
+///
+/// Every register, including [`Reg::CTX`], can be a member. Sets that
+/// describe architectural registers (clobbers, callee-saved registers) usually
+/// exclude [`Reg::CTX`]; [`RegSet::ALL_EXCEPT_CTX`] is the universe for those.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::reg::{Reg, RegSet};
+///
+/// const ARGS: RegSet = RegSet::from_regs(&[Reg(10), Reg(11), Reg(12)]);
+/// let live = RegSet::from_regs(&[Reg(11), Reg::CTX]);
+///
+/// assert!(ARGS.contains(Reg(12)));
+/// assert_eq!(ARGS & live, RegSet::single(Reg(11)));
+/// assert_eq!((ARGS | live).len(), 4);
+/// assert_eq!((live - ARGS).iter().collect::<Vec<_>>(), [Reg::CTX]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegSet {
+    bits: [u64; 4],
+}
+impl RegSet {
+    /// The set containing no registers.
+    pub const EMPTY: RegSet = RegSet { bits: [0; 4] };
+    /// The set containing every register, including [`Reg::CTX`].
+    pub const ALL: RegSet = RegSet {
+        bits: [u64::MAX; 4],
+    };
+    /// The set containing every register except [`Reg::CTX`].
+    pub const ALL_EXCEPT_CTX: RegSet = RegSet::ALL.without(Reg::CTX);
+
+    /// Returns the set containing only `reg`.
+    pub const fn single(reg: Reg) -> Self {
+        RegSet::EMPTY.with(reg)
+    }
+
+    /// Returns the set containing each register in `regs`.
+    pub const fn from_regs(regs: &[Reg]) -> Self {
+        let mut set = RegSet::EMPTY;
+        let mut i = 0;
+        while i < regs.len() {
+            set = set.with(regs[i]);
+            i += 1;
+        }
+        set
+    }
+
+    /// Returns this set with `reg` added.
+    pub const fn with(mut self, reg: Reg) -> Self {
+        self.bits[(reg.0 / 64) as usize] |= 1 << (reg.0 % 64);
+        self
+    }
+
+    /// Returns this set with `reg` removed.
+    pub const fn without(mut self, reg: Reg) -> Self {
+        self.bits[(reg.0 / 64) as usize] &= !(1 << (reg.0 % 64));
+        self
+    }
+
+    /// Returns `true` if `reg` is in this set.
+    pub const fn contains(&self, reg: Reg) -> bool {
+        self.bits[(reg.0 / 64) as usize] & (1 << (reg.0 % 64)) != 0
+    }
+
+    /// Adds `reg`, returning `true` if it was not already present.
+    pub fn insert(&mut self, reg: Reg) -> bool {
+        let present = self.contains(reg);
+        *self = self.with(reg);
+        !present
+    }
+
+    /// Removes `reg`, returning `true` if it was present.
+    pub fn remove(&mut self, reg: Reg) -> bool {
+        let present = self.contains(reg);
+        *self = self.without(reg);
+        present
+    }
+
+    /// Returns the registers in either set.
+    pub const fn union(self, other: RegSet) -> Self {
+        let mut i = 0;
+        let mut bits = self.bits;
+        while i < 4 {
+            bits[i] |= other.bits[i];
+            i += 1;
+        }
+        RegSet { bits }
+    }
+
+    /// Returns the registers in both sets.
+    pub const fn intersection(self, other: RegSet) -> Self {
+        let mut i = 0;
+        let mut bits = self.bits;
+        while i < 4 {
+            bits[i] &= other.bits[i];
+            i += 1;
+        }
+        RegSet { bits }
+    }
+
+    /// Returns the registers in this set but not in `other`.
+    pub const fn difference(self, other: RegSet) -> Self {
+        self.intersection(other.complement())
+    }
+
+    /// Returns the registers in exactly one of the sets.
+    pub const fn symmetric_difference(self, other: RegSet) -> Self {
+        self.union(other).difference(self.intersection(other))
+    }
+
+    /// Returns the registers not in this set.
+    pub const fn complement(self) -> Self {
+        RegSet {
+            bits: [!self.bits[0], !self.bits[1], !self.bits[2], !self.bits[3]],
+        }
+    }
+
+    /// Returns `true` if every register in this set is also in `other`.
+    pub const fn is_subset(&self, other: &RegSet) -> bool {
+        self.difference(*other).is_empty()
+    }
+
+    /// Returns `true` if the set contains no registers.
+    pub const fn is_empty(&self) -> bool {
+        self.bits[0] == 0 && self.bits[1] == 0 && self.bits[2] == 0 && self.bits[3] == 0
+    }
+
+    /// Returns the number of registers in the set.
+    pub const fn len(&self) -> usize {
+        (self.bits[0].count_ones()
+            + self.bits[1].count_ones()
+            + self.bits[2].count_ones()
+            + self.bits[3].count_ones()) as usize
+    }
+
+    /// Returns an iterator over the registers in ascending order.
+    pub fn iter(&self) -> RegSetIter {
+        RegSetIter { set: *self }
+    }
+}
+impl Debug for RegSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+impl BitOr for RegSet {
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+impl BitAnd for RegSet {
+    type Output = Self;
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+impl BitXor for RegSet {
+    type Output = Self;
+    fn bitxor(self, rhs: Self) -> Self {
+        self.symmetric_difference(rhs)
+    }
+}
+impl Sub for RegSet {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.difference(rhs)
+    }
+}
+impl Not for RegSet {
+    type Output = Self;
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+impl BitOrAssign for RegSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+impl BitAndAssign for RegSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+impl BitXorAssign for RegSet {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+impl SubAssign for RegSet {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+impl From<Reg> for RegSet {
+    fn from(value: Reg) -> Self {
+        RegSet::single(value)
+    }
+}
+impl FromIterator<Reg> for RegSet {
+    fn from_iter<T: IntoIterator<Item = Reg>>(iter: T) -> Self {
+        let mut set = RegSet::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+impl Extend<Reg> for RegSet {
+    fn extend<T: IntoIterator<Item = Reg>>(&mut self, iter: T) {
+        for reg in iter {
+            self.insert(reg);
+        }
+    }
+}
+impl IntoIterator for RegSet {
+    type Item = Reg;
+    type IntoIter = RegSetIter;
+    fn into_iter(self) -> RegSetIter {
+        self.iter()
+    }
+}
+impl IntoIterator for &RegSet {
+    type Item = Reg;
+    type IntoIter = RegSetIter;
+    fn into_iter(self) -> RegSetIter {
+        self.iter()
+    }
+}
+/// An iterator over the registers of a [`RegSet`] in ascending order.
+#[derive(Clone, Debug)]
+pub struct RegSetIter {
+    set: RegSet,
+}
+impl Iterator for RegSetIter {
+    type Item = Reg;
+    fn next(&mut self) -> Option<Reg> {
+        let (i, word) = self
+            .set
+            .bits
+            .iter_mut()
+            .enumerate()
+            .find(|(_, w)| **w != 0)?;
+        let bit = word.trailing_zeros();
+        *word &= *word - 1;
+        Some(Reg((i as u32 * 64 + bit) as u8))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.set.len(), Some(self.set.len()))
+    }
+}
+impl DoubleEndedIterator for RegSetIter {
+    fn next_back(&mut self) -> Option<Reg> {
+        let (i, word) = self
+            .set
+            .bits
+            .iter_mut()
+            .enumerate()
+            .rfind(|(_, w)| **w != 0)?;
+        let bit = 63 - word.leading_zeros();
+        *word &= !(1 << bit);
+        Some(Reg((i as u32 * 64 + bit) as u8))
+    }
+}
+impl ExactSizeIterator for RegSetIter {}
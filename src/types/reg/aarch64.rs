@@ -0,0 +1,109 @@
+//! AArch64 general-purpose registers.
+//!
+//! `x0` to `x30` are `Reg(0)` to `Reg(30)`. Encoding 31 names either the zero
+//! register or the stack pointer depending on the instruction, so `xzr` is
+//! `Reg(31)` and `sp` is `Reg(32)`. [`Reg::r32_swap_0_and_31`] converts `x0`
+//! to `x30` and `xzr` to a layout where the zero register is `Reg(0)`, as on
+//! RISC-V and MIPS. It works modulo 32, so it maps `sp` to `Reg(31)` just like
+//! `x0`; handle `sp` before swapping.
+//!
+//! ```
+//! use portal_pc_asm_common::types::reg::aarch64::{x, SP, XZR};
+//! use portal_pc_asm_common::types::reg::Reg;
+//!
+//! assert_eq!(XZR.r32_swap_0_and_31(), Reg(0));
+//! assert_eq!(x(0).r32_swap_0_and_31(), Reg(31));
+//! assert_eq!(SP.r32_swap_0_and_31(), x(0).r32_swap_0_and_31());
+//! ```
+
+use super::*;
+
+/// The frame pointer, `x29`
+pub const FP: Reg = Reg(29);
+/// The link register, `x30`
+pub const LR: Reg = Reg(30);
+/// The zero register, `xzr`
+pub const XZR: Reg = Reg(31);
+/// The stack pointer, `sp`
+pub const SP: Reg = Reg(32);
+
+/// Returns the general-purpose register `x{n}`, for `n` up to 30.
+pub const fn x(n: u8) -> Reg {
+    Reg(n)
+}
+
+/// The AArch64 register names.
+pub static REGS: RegMap = RegMap {
+    full: Bitness::I64,
+    extending_writes: &[(Bitness::I32, Ext::Zero)],
+    names: &[
+        RegName::new("x0", Reg(0), Bitness::I64, 0, RegClass::General),
+        RegName::new("x1", Reg(1), Bitness::I64, 1, RegClass::General),
+        RegName::new("x2", Reg(2), Bitness::I64, 2, RegClass::General),
+        RegName::new("x3", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("x4", Reg(4), Bitness::I64, 4, RegClass::General),
+        RegName::new("x5", Reg(5), Bitness::I64, 5, RegClass::General),
+        RegName::new("x6", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("x7", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("x8", Reg(8), Bitness::I64, 8, RegClass::General),
+        RegName::new("x9", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("x10", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("x11", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("x12", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("x13", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("x14", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("x15", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("x16", Reg(16), Bitness::I64, 16, RegClass::General),
+        RegName::new("x17", Reg(17), Bitness::I64, 17, RegClass::General),
+        RegName::new("x18", Reg(18), Bitness::I64, 18, RegClass::General),
+        RegName::new("x19", Reg(19), Bitness::I64, 19, RegClass::General),
+        RegName::new("x20", Reg(20), Bitness::I64, 20, RegClass::General),
+        RegName::new("x21", Reg(21), Bitness::I64, 21, RegClass::General),
+        RegName::new("x22", Reg(22), Bitness::I64, 22, RegClass::General),
+        RegName::new("x23", Reg(23), Bitness::I64, 23, RegClass::General),
+        RegName::new("x24", Reg(24), Bitness::I64, 24, RegClass::General),
+        RegName::new("x25", Reg(25), Bitness::I64, 25, RegClass::General),
+        RegName::new("x26", Reg(26), Bitness::I64, 26, RegClass::General),
+        RegName::new("x27", Reg(27), Bitness::I64, 27, RegClass::General),
+        RegName::new("x28", Reg(28), Bitness::I64, 28, RegClass::General),
+        RegName::new("x29", Reg(29), Bitness::I64, 29, RegClass::FramePointer),
+        RegName::new("x30", Reg(30), Bitness::I64, 30, RegClass::Link),
+        RegName::new("xzr", Reg(31), Bitness::I64, 31, RegClass::Zero),
+        RegName::new("sp", Reg(32), Bitness::I64, 31, RegClass::StackPointer),
+        RegName::new("w0", Reg(0), Bitness::I32, 0, RegClass::General),
+        RegName::new("w1", Reg(1), Bitness::I32, 1, RegClass::General),
+        RegName::new("w2", Reg(2), Bitness::I32, 2, RegClass::General),
+        RegName::new("w3", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("w4", Reg(4), Bitness::I32, 4, RegClass::General),
+        RegName::new("w5", Reg(5), Bitness::I32, 5, RegClass::General),
+        RegName::new("w6", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("w7", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("w8", Reg(8), Bitness::I32, 8, RegClass::General),
+        RegName::new("w9", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("w10", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("w11", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("w12", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("w13", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("w14", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("w15", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("w16", Reg(16), Bitness::I32, 16, RegClass::General),
+        RegName::new("w17", Reg(17), Bitness::I32, 17, RegClass::General),
+        RegName::new("w18", Reg(18), Bitness::I32, 18, RegClass::General),
+        RegName::new("w19", Reg(19), Bitness::I32, 19, RegClass::General),
+        RegName::new("w20", Reg(20), Bitness::I32, 20, RegClass::General),
+        RegName::new("w21", Reg(21), Bitness::I32, 21, RegClass::General),
+        RegName::new("w22", Reg(22), Bitness::I32, 22, RegClass::General),
+        RegName::new("w23", Reg(23), Bitness::I32, 23, RegClass::General),
+        RegName::new("w24", Reg(24), Bitness::I32, 24, RegClass::General),
+        RegName::new("w25", Reg(25), Bitness::I32, 25, RegClass::General),
+        RegName::new("w26", Reg(26), Bitness::I32, 26, RegClass::General),
+        RegName::new("w27", Reg(27), Bitness::I32, 27, RegClass::General),
+        RegName::new("w28", Reg(28), Bitness::I32, 28, RegClass::General),
+        RegName::new("w29", Reg(29), Bitness::I32, 29, RegClass::FramePointer),
+        RegName::new("w30", Reg(30), Bitness::I32, 30, RegClass::Link),
+        RegName::new("wzr", Reg(31), Bitness::I32, 31, RegClass::Zero),
+        RegName::new("wsp", Reg(32), Bitness::I32, 31, RegClass::StackPointer),
+        RegName::new("fp", Reg(29), Bitness::I64, 29, RegClass::FramePointer),
+        RegName::new("lr", Reg(30), Bitness::I64, 30, RegClass::Link),
+    ],
+};
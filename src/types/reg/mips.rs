@@ -0,0 +1,219 @@
+//! MIPS general-purpose registers.
+//!
+//! `$0` to `$31` are `Reg(0)` to `Reg(31)`. Names are stored without the `$`
+//! sigil; [`RegMap::by_name`] accepts them with or without it. Both the
+//! conventional names (`zero`, `at`, ..., `ra`) and the numeric names are
+//! accepted, with the conventional names being canonical.
+
+use super::*;
+
+/// `$zero`
+pub const ZERO: Reg = Reg(0);
+/// `$at`
+pub const AT: Reg = Reg(1);
+/// `$v0`
+pub const V0: Reg = Reg(2);
+/// `$v1`
+pub const V1: Reg = Reg(3);
+/// `$a0`
+pub const A0: Reg = Reg(4);
+/// `$a1`
+pub const A1: Reg = Reg(5);
+/// `$a2`
+pub const A2: Reg = Reg(6);
+/// `$a3`
+pub const A3: Reg = Reg(7);
+/// `$t0`
+pub const T0: Reg = Reg(8);
+/// `$t1`
+pub const T1: Reg = Reg(9);
+/// `$t2`
+pub const T2: Reg = Reg(10);
+/// `$t3`
+pub const T3: Reg = Reg(11);
+/// `$t4`
+pub const T4: Reg = Reg(12);
+/// `$t5`
+pub const T5: Reg = Reg(13);
+/// `$t6`
+pub const T6: Reg = Reg(14);
+/// `$t7`
+pub const T7: Reg = Reg(15);
+/// `$s0`
+pub const S0: Reg = Reg(16);
+/// `$s1`
+pub const S1: Reg = Reg(17);
+/// `$s2`
+pub const S2: Reg = Reg(18);
+/// `$s3`
+pub const S3: Reg = Reg(19);
+/// `$s4`
+pub const S4: Reg = Reg(20);
+/// `$s5`
+pub const S5: Reg = Reg(21);
+/// `$s6`
+pub const S6: Reg = Reg(22);
+/// `$s7`
+pub const S7: Reg = Reg(23);
+/// `$t8`
+pub const T8: Reg = Reg(24);
+/// `$t9`
+pub const T9: Reg = Reg(25);
+/// `$k0`
+pub const K0: Reg = Reg(26);
+/// `$k1`
+pub const K1: Reg = Reg(27);
+/// `$gp`
+pub const GP: Reg = Reg(28);
+/// `$sp`
+pub const SP: Reg = Reg(29);
+/// `$fp`
+pub const FP: Reg = Reg(30);
+/// `$ra`
+pub const RA: Reg = Reg(31);
+
+/// The MIPS32 register names.
+pub static MIPS32: RegMap = RegMap {
+    full: Bitness::I32,
+    extending_writes: &[],
+    names: &[
+        RegName::new("zero", Reg(0), Bitness::I32, 0, RegClass::Zero),
+        RegName::new("at", Reg(1), Bitness::I32, 1, RegClass::General),
+        RegName::new("v0", Reg(2), Bitness::I32, 2, RegClass::General),
+        RegName::new("v1", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("a0", Reg(4), Bitness::I32, 4, RegClass::General),
+        RegName::new("a1", Reg(5), Bitness::I32, 5, RegClass::General),
+        RegName::new("a2", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("a3", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("t0", Reg(8), Bitness::I32, 8, RegClass::General),
+        RegName::new("t1", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("t2", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("t3", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("t4", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("t5", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("t6", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("t7", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("s0", Reg(16), Bitness::I32, 16, RegClass::General),
+        RegName::new("s1", Reg(17), Bitness::I32, 17, RegClass::General),
+        RegName::new("s2", Reg(18), Bitness::I32, 18, RegClass::General),
+        RegName::new("s3", Reg(19), Bitness::I32, 19, RegClass::General),
+        RegName::new("s4", Reg(20), Bitness::I32, 20, RegClass::General),
+        RegName::new("s5", Reg(21), Bitness::I32, 21, RegClass::General),
+        RegName::new("s6", Reg(22), Bitness::I32, 22, RegClass::General),
+        RegName::new("s7", Reg(23), Bitness::I32, 23, RegClass::General),
+        RegName::new("t8", Reg(24), Bitness::I32, 24, RegClass::General),
+        RegName::new("t9", Reg(25), Bitness::I32, 25, RegClass::General),
+        RegName::new("k0", Reg(26), Bitness::I32, 26, RegClass::General),
+        RegName::new("k1", Reg(27), Bitness::I32, 27, RegClass::General),
+        RegName::new("gp", Reg(28), Bitness::I32, 28, RegClass::General),
+        RegName::new("sp", Reg(29), Bitness::I32, 29, RegClass::StackPointer),
+        RegName::new("fp", Reg(30), Bitness::I32, 30, RegClass::FramePointer),
+        RegName::new("ra", Reg(31), Bitness::I32, 31, RegClass::Link),
+        RegName::new("s8", Reg(30), Bitness::I32, 30, RegClass::FramePointer),
+        RegName::new("0", Reg(0), Bitness::I32, 0, RegClass::Zero),
+        RegName::new("1", Reg(1), Bitness::I32, 1, RegClass::General),
+        RegName::new("2", Reg(2), Bitness::I32, 2, RegClass::General),
+        RegName::new("3", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("4", Reg(4), Bitness::I32, 4, RegClass::General),
+        RegName::new("5", Reg(5), Bitness::I32, 5, RegClass::General),
+        RegName::new("6", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("7", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("8", Reg(8), Bitness::I32, 8, RegClass::General),
+        RegName::new("9", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("10", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("11", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("12", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("13", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("14", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("15", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("16", Reg(16), Bitness::I32, 16, RegClass::General),
+        RegName::new("17", Reg(17), Bitness::I32, 17, RegClass::General),
+        RegName::new("18", Reg(18), Bitness::I32, 18, RegClass::General),
+        RegName::new("19", Reg(19), Bitness::I32, 19, RegClass::General),
+        RegName::new("20", Reg(20), Bitness::I32, 20, RegClass::General),
+        RegName::new("21", Reg(21), Bitness::I32, 21, RegClass::General),
+        RegName::new("22", Reg(22), Bitness::I32, 22, RegClass::General),
+        RegName::new("23", Reg(23), Bitness::I32, 23, RegClass::General),
+        RegName::new("24", Reg(24), Bitness::I32, 24, RegClass::General),
+        RegName::new("25", Reg(25), Bitness::I32, 25, RegClass::General),
+        RegName::new("26", Reg(26), Bitness::I32, 26, RegClass::General),
+        RegName::new("27", Reg(27), Bitness::I32, 27, RegClass::General),
+        RegName::new("28", Reg(28), Bitness::I32, 28, RegClass::General),
+        RegName::new("29", Reg(29), Bitness::I32, 29, RegClass::StackPointer),
+        RegName::new("30", Reg(30), Bitness::I32, 30, RegClass::FramePointer),
+        RegName::new("31", Reg(31), Bitness::I32, 31, RegClass::Link),
+    ],
+};
+/// The MIPS64 register names.
+pub static MIPS64: RegMap = RegMap {
+    full: Bitness::I64,
+    // 32-bit operations sign-extend their result.
+    extending_writes: &[(Bitness::I32, Ext::Sign)],
+    names: &[
+        RegName::new("zero", Reg(0), Bitness::I64, 0, RegClass::Zero),
+        RegName::new("at", Reg(1), Bitness::I64, 1, RegClass::General),
+        RegName::new("v0", Reg(2), Bitness::I64, 2, RegClass::General),
+        RegName::new("v1", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("a0", Reg(4), Bitness::I64, 4, RegClass::General),
+        RegName::new("a1", Reg(5), Bitness::I64, 5, RegClass::General),
+        RegName::new("a2", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("a3", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("t0", Reg(8), Bitness::I64, 8, RegClass::General),
+        RegName::new("t1", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("t2", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("t3", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("t4", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("t5", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("t6", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("t7", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("s0", Reg(16), Bitness::I64, 16, RegClass::General),
+        RegName::new("s1", Reg(17), Bitness::I64, 17, RegClass::General),
+        RegName::new("s2", Reg(18), Bitness::I64, 18, RegClass::General),
+        RegName::new("s3", Reg(19), Bitness::I64, 19, RegClass::General),
+        RegName::new("s4", Reg(20), Bitness::I64, 20, RegClass::General),
+        RegName::new("s5", Reg(21), Bitness::I64, 21, RegClass::General),
+        RegName::new("s6", Reg(22), Bitness::I64, 22, RegClass::General),
+        RegName::new("s7", Reg(23), Bitness::I64, 23, RegClass::General),
+        RegName::new("t8", Reg(24), Bitness::I64, 24, RegClass::General),
+        RegName::new("t9", Reg(25), Bitness::I64, 25, RegClass::General),
+        RegName::new("k0", Reg(26), Bitness::I64, 26, RegClass::General),
+        RegName::new("k1", Reg(27), Bitness::I64, 27, RegClass::General),
+        RegName::new("gp", Reg(28), Bitness::I64, 28, RegClass::General),
+        RegName::new("sp", Reg(29), Bitness::I64, 29, RegClass::StackPointer),
+        RegName::new("fp", Reg(30), Bitness::I64, 30, RegClass::FramePointer),
+        RegName::new("ra", Reg(31), Bitness::I64, 31, RegClass::Link),
+        RegName::new("s8", Reg(30), Bitness::I64, 30, RegClass::FramePointer),
+        RegName::new("0", Reg(0), Bitness::I64, 0, RegClass::Zero),
+        RegName::new("1", Reg(1), Bitness::I64, 1, RegClass::General),
+        RegName::new("2", Reg(2), Bitness::I64, 2, RegClass::General),
+        RegName::new("3", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("4", Reg(4), Bitness::I64, 4, RegClass::General),
+        RegName::new("5", Reg(5), Bitness::I64, 5, RegClass::General),
+        RegName::new("6", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("7", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("8", Reg(8), Bitness::I64, 8, RegClass::General),
+        RegName::new("9", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("10", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("11", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("12", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("13", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("14", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("15", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("16", Reg(16), Bitness::I64, 16, RegClass::General),
+        RegName::new("17", Reg(17), Bitness::I64, 17, RegClass::General),
+        RegName::new("18", Reg(18), Bitness::I64, 18, RegClass::General),
+        RegName::new("19", Reg(19), Bitness::I64, 19, RegClass::General),
+        RegName::new("20", Reg(20), Bitness::I64, 20, RegClass::General),
+        RegName::new("21", Reg(21), Bitness::I64, 21, RegClass::General),
+        RegName::new("22", Reg(22), Bitness::I64, 22, RegClass::General),
+        RegName::new("23", Reg(23), Bitness::I64, 23, RegClass::General),
+        RegName::new("24", Reg(24), Bitness::I64, 24, RegClass::General),
+        RegName::new("25", Reg(25), Bitness::I64, 25, RegClass::General),
+        RegName::new("26", Reg(26), Bitness::I64, 26, RegClass::General),
+        RegName::new("27", Reg(27), Bitness::I64, 27, RegClass::General),
+        RegName::new("28", Reg(28), Bitness::I64, 28, RegClass::General),
+        RegName::new("29", Reg(29), Bitness::I64, 29, RegClass::StackPointer),
+        RegName::new("30", Reg(30), Bitness::I64, 30, RegClass::FramePointer),
+        RegName::new("31", Reg(31), Bitness::I64, 31, RegClass::Link),
+    ],
+};
@@ -0,0 +1,218 @@
+//! RISC-V integer registers.
+//!
+//! `x0` to `x31` are `Reg(0)` to `Reg(31)`. Both the ABI names (`zero`, `ra`,
+//! `sp`, ..., `t6`) and the numeric names are accepted, with the ABI names
+//! being canonical. RV32 and RV64 differ only in the register width.
+
+use super::*;
+
+/// `zero`
+pub const ZERO: Reg = Reg(0);
+/// `ra`
+pub const RA: Reg = Reg(1);
+/// `sp`
+pub const SP: Reg = Reg(2);
+/// `gp`
+pub const GP: Reg = Reg(3);
+/// `tp`
+pub const TP: Reg = Reg(4);
+/// `t0`
+pub const T0: Reg = Reg(5);
+/// `t1`
+pub const T1: Reg = Reg(6);
+/// `t2`
+pub const T2: Reg = Reg(7);
+/// `s0`
+pub const S0: Reg = Reg(8);
+/// `s1`
+pub const S1: Reg = Reg(9);
+/// `a0`
+pub const A0: Reg = Reg(10);
+/// `a1`
+pub const A1: Reg = Reg(11);
+/// `a2`
+pub const A2: Reg = Reg(12);
+/// `a3`
+pub const A3: Reg = Reg(13);
+/// `a4`
+pub const A4: Reg = Reg(14);
+/// `a5`
+pub const A5: Reg = Reg(15);
+/// `a6`
+pub const A6: Reg = Reg(16);
+/// `a7`
+pub const A7: Reg = Reg(17);
+/// `s2`
+pub const S2: Reg = Reg(18);
+/// `s3`
+pub const S3: Reg = Reg(19);
+/// `s4`
+pub const S4: Reg = Reg(20);
+/// `s5`
+pub const S5: Reg = Reg(21);
+/// `s6`
+pub const S6: Reg = Reg(22);
+/// `s7`
+pub const S7: Reg = Reg(23);
+/// `s8`
+pub const S8: Reg = Reg(24);
+/// `s9`
+pub const S9: Reg = Reg(25);
+/// `s10`
+pub const S10: Reg = Reg(26);
+/// `s11`
+pub const S11: Reg = Reg(27);
+/// `t3`
+pub const T3: Reg = Reg(28);
+/// `t4`
+pub const T4: Reg = Reg(29);
+/// `t5`
+pub const T5: Reg = Reg(30);
+/// `t6`
+pub const T6: Reg = Reg(31);
+
+/// The RV32 register names.
+pub static RV32: RegMap = RegMap {
+    full: Bitness::I32,
+    extending_writes: &[],
+    names: &[
+        RegName::new("zero", Reg(0), Bitness::I32, 0, RegClass::Zero),
+        RegName::new("ra", Reg(1), Bitness::I32, 1, RegClass::Link),
+        RegName::new("sp", Reg(2), Bitness::I32, 2, RegClass::StackPointer),
+        RegName::new("gp", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("tp", Reg(4), Bitness::I32, 4, RegClass::General),
+        RegName::new("t0", Reg(5), Bitness::I32, 5, RegClass::General),
+        RegName::new("t1", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("t2", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("s0", Reg(8), Bitness::I32, 8, RegClass::FramePointer),
+        RegName::new("s1", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("a0", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("a1", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("a2", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("a3", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("a4", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("a5", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("a6", Reg(16), Bitness::I32, 16, RegClass::General),
+        RegName::new("a7", Reg(17), Bitness::I32, 17, RegClass::General),
+        RegName::new("s2", Reg(18), Bitness::I32, 18, RegClass::General),
+        RegName::new("s3", Reg(19), Bitness::I32, 19, RegClass::General),
+        RegName::new("s4", Reg(20), Bitness::I32, 20, RegClass::General),
+        RegName::new("s5", Reg(21), Bitness::I32, 21, RegClass::General),
+        RegName::new("s6", Reg(22), Bitness::I32, 22, RegClass::General),
+        RegName::new("s7", Reg(23), Bitness::I32, 23, RegClass::General),
+        RegName::new("s8", Reg(24), Bitness::I32, 24, RegClass::General),
+        RegName::new("s9", Reg(25), Bitness::I32, 25, RegClass::General),
+        RegName::new("s10", Reg(26), Bitness::I32, 26, RegClass::General),
+        RegName::new("s11", Reg(27), Bitness::I32, 27, RegClass::General),
+        RegName::new("t3", Reg(28), Bitness::I32, 28, RegClass::General),
+        RegName::new("t4", Reg(29), Bitness::I32, 29, RegClass::General),
+        RegName::new("t5", Reg(30), Bitness::I32, 30, RegClass::General),
+        RegName::new("t6", Reg(31), Bitness::I32, 31, RegClass::General),
+        RegName::new("fp", Reg(8), Bitness::I32, 8, RegClass::FramePointer),
+        RegName::new("x0", Reg(0), Bitness::I32, 0, RegClass::Zero),
+        RegName::new("x1", Reg(1), Bitness::I32, 1, RegClass::Link),
+        RegName::new("x2", Reg(2), Bitness::I32, 2, RegClass::StackPointer),
+        RegName::new("x3", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("x4", Reg(4), Bitness::I32, 4, RegClass::General),
+        RegName::new("x5", Reg(5), Bitness::I32, 5, RegClass::General),
+        RegName::new("x6", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("x7", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("x8", Reg(8), Bitness::I32, 8, RegClass::FramePointer),
+        RegName::new("x9", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("x10", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("x11", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("x12", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("x13", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("x14", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("x15", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("x16", Reg(16), Bitness::I32, 16, RegClass::General),
+        RegName::new("x17", Reg(17), Bitness::I32, 17, RegClass::General),
+        RegName::new("x18", Reg(18), Bitness::I32, 18, RegClass::General),
+        RegName::new("x19", Reg(19), Bitness::I32, 19, RegClass::General),
+        RegName::new("x20", Reg(20), Bitness::I32, 20, RegClass::General),
+        RegName::new("x21", Reg(21), Bitness::I32, 21, RegClass::General),
+        RegName::new("x22", Reg(22), Bitness::I32, 22, RegClass::General),
+        RegName::new("x23", Reg(23), Bitness::I32, 23, RegClass::General),
+        RegName::new("x24", Reg(24), Bitness::I32, 24, RegClass::General),
+        RegName::new("x25", Reg(25), Bitness::I32, 25, RegClass::General),
+        RegName::new("x26", Reg(26), Bitness::I32, 26, RegClass::General),
+        RegName::new("x27", Reg(27), Bitness::I32, 27, RegClass::General),
+        RegName::new("x28", Reg(28), Bitness::I32, 28, RegClass::General),
+        RegName::new("x29", Reg(29), Bitness::I32, 29, RegClass::General),
+        RegName::new("x30", Reg(30), Bitness::I32, 30, RegClass::General),
+        RegName::new("x31", Reg(31), Bitness::I32, 31, RegClass::General),
+    ],
+};
+/// The RV64 register names.
+pub static RV64: RegMap = RegMap {
+    full: Bitness::I64,
+    // The `*w` instructions sign-extend their 32-bit result.
+    extending_writes: &[(Bitness::I32, Ext::Sign)],
+    names: &[
+        RegName::new("zero", Reg(0), Bitness::I64, 0, RegClass::Zero),
+        RegName::new("ra", Reg(1), Bitness::I64, 1, RegClass::Link),
+        RegName::new("sp", Reg(2), Bitness::I64, 2, RegClass::StackPointer),
+        RegName::new("gp", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("tp", Reg(4), Bitness::I64, 4, RegClass::General),
+        RegName::new("t0", Reg(5), Bitness::I64, 5, RegClass::General),
+        RegName::new("t1", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("t2", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("s0", Reg(8), Bitness::I64, 8, RegClass::FramePointer),
+        RegName::new("s1", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("a0", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("a1", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("a2", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("a3", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("a4", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("a5", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("a6", Reg(16), Bitness::I64, 16, RegClass::General),
+        RegName::new("a7", Reg(17), Bitness::I64, 17, RegClass::General),
+        RegName::new("s2", Reg(18), Bitness::I64, 18, RegClass::General),
+        RegName::new("s3", Reg(19), Bitness::I64, 19, RegClass::General),
+        RegName::new("s4", Reg(20), Bitness::I64, 20, RegClass::General),
+        RegName::new("s5", Reg(21), Bitness::I64, 21, RegClass::General),
+        RegName::new("s6", Reg(22), Bitness::I64, 22, RegClass::General),
+        RegName::new("s7", Reg(23), Bitness::I64, 23, RegClass::General),
+        RegName::new("s8", Reg(24), Bitness::I64, 24, RegClass::General),
+        RegName::new("s9", Reg(25), Bitness::I64, 25, RegClass::General),
+        RegName::new("s10", Reg(26), Bitness::I64, 26, RegClass::General),
+        RegName::new("s11", Reg(27), Bitness::I64, 27, RegClass::General),
+        RegName::new("t3", Reg(28), Bitness::I64, 28, RegClass::General),
+        RegName::new("t4", Reg(29), Bitness::I64, 29, RegClass::General),
+        RegName::new("t5", Reg(30), Bitness::I64, 30, RegClass::General),
+        RegName::new("t6", Reg(31), Bitness::I64, 31, RegClass::General),
+        RegName::new("fp", Reg(8), Bitness::I64, 8, RegClass::FramePointer),
+        RegName::new("x0", Reg(0), Bitness::I64, 0, RegClass::Zero),
+        RegName::new("x1", Reg(1), Bitness::I64, 1, RegClass::Link),
+        RegName::new("x2", Reg(2), Bitness::I64, 2, RegClass::StackPointer),
+        RegName::new("x3", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("x4", Reg(4), Bitness::I64, 4, RegClass::General),
+        RegName::new("x5", Reg(5), Bitness::I64, 5, RegClass::General),
+        RegName::new("x6", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("x7", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("x8", Reg(8), Bitness::I64, 8, RegClass::FramePointer),
+        RegName::new("x9", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("x10", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("x11", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("x12", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("x13", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("x14", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("x15", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("x16", Reg(16), Bitness::I64, 16, RegClass::General),
+        RegName::new("x17", Reg(17), Bitness::I64, 17, RegClass::General),
+        RegName::new("x18", Reg(18), Bitness::I64, 18, RegClass::General),
+        RegName::new("x19", Reg(19), Bitness::I64, 19, RegClass::General),
+        RegName::new("x20", Reg(20), Bitness::I64, 20, RegClass::General),
+        RegName::new("x21", Reg(21), Bitness::I64, 21, RegClass::General),
+        RegName::new("x22", Reg(22), Bitness::I64, 22, RegClass::General),
+        RegName::new("x23", Reg(23), Bitness::I64, 23, RegClass::General),
+        RegName::new("x24", Reg(24), Bitness::I64, 24, RegClass::General),
+        RegName::new("x25", Reg(25), Bitness::I64, 25, RegClass::General),
+        RegName::new("x26", Reg(26), Bitness::I64, 26, RegClass::General),
+        RegName::new("x27", Reg(27), Bitness::I64, 27, RegClass::General),
+        RegName::new("x28", Reg(28), Bitness::I64, 28, RegClass::General),
+        RegName::new("x29", Reg(29), Bitness::I64, 29, RegClass::General),
+        RegName::new("x30", Reg(30), Bitness::I64, 30, RegClass::General),
+        RegName::new("x31", Reg(31), Bitness::I64, 31, RegClass::General),
+    ],
+};
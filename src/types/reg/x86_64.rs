@@ -0,0 +1,131 @@
+//! x86-64 general-purpose registers.
+//!
+//! [`Reg`] numbers follow the hardware encoding, with the REX extension bit
+//! as bit 3, so `rax` is `Reg(0)` and `r15` is `Reg(15)`. The 32-, 16- and
+//! 8-bit names are sub-registers of the 64-bit registers; `ah`, `ch`, `dh`
+//! and `bh` occupy bits 8 to 15 and are only encodable without a REX prefix.
+
+use super::*;
+
+/// `rax`
+pub const RAX: Reg = Reg(0);
+/// `rcx`
+pub const RCX: Reg = Reg(1);
+/// `rdx`
+pub const RDX: Reg = Reg(2);
+/// `rbx`
+pub const RBX: Reg = Reg(3);
+/// `rsp`
+pub const RSP: Reg = Reg(4);
+/// `rbp`
+pub const RBP: Reg = Reg(5);
+/// `rsi`
+pub const RSI: Reg = Reg(6);
+/// `rdi`
+pub const RDI: Reg = Reg(7);
+/// `r8`
+pub const R8: Reg = Reg(8);
+/// `r9`
+pub const R9: Reg = Reg(9);
+/// `r10`
+pub const R10: Reg = Reg(10);
+/// `r11`
+pub const R11: Reg = Reg(11);
+/// `r12`
+pub const R12: Reg = Reg(12);
+/// `r13`
+pub const R13: Reg = Reg(13);
+/// `r14`
+pub const R14: Reg = Reg(14);
+/// `r15`
+pub const R15: Reg = Reg(15);
+
+/// The x86-64 register names.
+pub static REGS: RegMap = RegMap {
+    full: Bitness::I64,
+    // Writing a 32-bit register clears bits 32..64; 8- and 16-bit writes
+    // leave the rest of the register unchanged.
+    extending_writes: &[(Bitness::I32, Ext::Zero)],
+    names: &[
+        RegName::new("rax", Reg(0), Bitness::I64, 0, RegClass::General),
+        RegName::new("rcx", Reg(1), Bitness::I64, 1, RegClass::General),
+        RegName::new("rdx", Reg(2), Bitness::I64, 2, RegClass::General),
+        RegName::new("rbx", Reg(3), Bitness::I64, 3, RegClass::General),
+        RegName::new("rsp", Reg(4), Bitness::I64, 4, RegClass::StackPointer),
+        RegName::new("rbp", Reg(5), Bitness::I64, 5, RegClass::FramePointer),
+        RegName::new("rsi", Reg(6), Bitness::I64, 6, RegClass::General),
+        RegName::new("rdi", Reg(7), Bitness::I64, 7, RegClass::General),
+        RegName::new("r8", Reg(8), Bitness::I64, 8, RegClass::General),
+        RegName::new("r9", Reg(9), Bitness::I64, 9, RegClass::General),
+        RegName::new("r10", Reg(10), Bitness::I64, 10, RegClass::General),
+        RegName::new("r11", Reg(11), Bitness::I64, 11, RegClass::General),
+        RegName::new("r12", Reg(12), Bitness::I64, 12, RegClass::General),
+        RegName::new("r13", Reg(13), Bitness::I64, 13, RegClass::General),
+        RegName::new("r14", Reg(14), Bitness::I64, 14, RegClass::General),
+        RegName::new("r15", Reg(15), Bitness::I64, 15, RegClass::General),
+        RegName::new("eax", Reg(0), Bitness::I32, 0, RegClass::General),
+        RegName::new("ecx", Reg(1), Bitness::I32, 1, RegClass::General),
+        RegName::new("edx", Reg(2), Bitness::I32, 2, RegClass::General),
+        RegName::new("ebx", Reg(3), Bitness::I32, 3, RegClass::General),
+        RegName::new("esp", Reg(4), Bitness::I32, 4, RegClass::StackPointer),
+        RegName::new("ebp", Reg(5), Bitness::I32, 5, RegClass::FramePointer),
+        RegName::new("esi", Reg(6), Bitness::I32, 6, RegClass::General),
+        RegName::new("edi", Reg(7), Bitness::I32, 7, RegClass::General),
+        RegName::new("r8d", Reg(8), Bitness::I32, 8, RegClass::General),
+        RegName::new("r9d", Reg(9), Bitness::I32, 9, RegClass::General),
+        RegName::new("r10d", Reg(10), Bitness::I32, 10, RegClass::General),
+        RegName::new("r11d", Reg(11), Bitness::I32, 11, RegClass::General),
+        RegName::new("r12d", Reg(12), Bitness::I32, 12, RegClass::General),
+        RegName::new("r13d", Reg(13), Bitness::I32, 13, RegClass::General),
+        RegName::new("r14d", Reg(14), Bitness::I32, 14, RegClass::General),
+        RegName::new("r15d", Reg(15), Bitness::I32, 15, RegClass::General),
+        RegName::new("ax", Reg(0), Bitness::I16, 0, RegClass::General),
+        RegName::new("cx", Reg(1), Bitness::I16, 1, RegClass::General),
+        RegName::new("dx", Reg(2), Bitness::I16, 2, RegClass::General),
+        RegName::new("bx", Reg(3), Bitness::I16, 3, RegClass::General),
+        RegName::new("sp", Reg(4), Bitness::I16, 4, RegClass::StackPointer),
+        RegName::new("bp", Reg(5), Bitness::I16, 5, RegClass::FramePointer),
+        RegName::new("si", Reg(6), Bitness::I16, 6, RegClass::General),
+        RegName::new("di", Reg(7), Bitness::I16, 7, RegClass::General),
+        RegName::new("r8w", Reg(8), Bitness::I16, 8, RegClass::General),
+        RegName::new("r9w", Reg(9), Bitness::I16, 9, RegClass::General),
+        RegName::new("r10w", Reg(10), Bitness::I16, 10, RegClass::General),
+        RegName::new("r11w", Reg(11), Bitness::I16, 11, RegClass::General),
+        RegName::new("r12w", Reg(12), Bitness::I16, 12, RegClass::General),
+        RegName::new("r13w", Reg(13), Bitness::I16, 13, RegClass::General),
+        RegName::new("r14w", Reg(14), Bitness::I16, 14, RegClass::General),
+        RegName::new("r15w", Reg(15), Bitness::I16, 15, RegClass::General),
+        RegName::new("al", Reg(0), Bitness::I8, 0, RegClass::General),
+        RegName::new("cl", Reg(1), Bitness::I8, 1, RegClass::General),
+        RegName::new("dl", Reg(2), Bitness::I8, 2, RegClass::General),
+        RegName::new("bl", Reg(3), Bitness::I8, 3, RegClass::General),
+        RegName::new("spl", Reg(4), Bitness::I8, 4, RegClass::StackPointer),
+        RegName::new("bpl", Reg(5), Bitness::I8, 5, RegClass::FramePointer),
+        RegName::new("sil", Reg(6), Bitness::I8, 6, RegClass::General),
+        RegName::new("dil", Reg(7), Bitness::I8, 7, RegClass::General),
+        RegName::new("r8b", Reg(8), Bitness::I8, 8, RegClass::General),
+        RegName::new("r9b", Reg(9), Bitness::I8, 9, RegClass::General),
+        RegName::new("r10b", Reg(10), Bitness::I8, 10, RegClass::General),
+        RegName::new("r11b", Reg(11), Bitness::I8, 11, RegClass::General),
+        RegName::new("r12b", Reg(12), Bitness::I8, 12, RegClass::General),
+        RegName::new("r13b", Reg(13), Bitness::I8, 13, RegClass::General),
+        RegName::new("r14b", Reg(14), Bitness::I8, 14, RegClass::General),
+        RegName::new("r15b", Reg(15), Bitness::I8, 15, RegClass::General),
+        RegName {
+            bit_offset: 8,
+            ..RegName::new("ah", Reg(0), Bitness::I8, 4, RegClass::General)
+        },
+        RegName {
+            bit_offset: 8,
+            ..RegName::new("ch", Reg(1), Bitness::I8, 5, RegClass::General)
+        },
+        RegName {
+            bit_offset: 8,
+            ..RegName::new("dh", Reg(2), Bitness::I8, 6, RegClass::General)
+        },
+        RegName {
+            bit_offset: 8,
+            ..RegName::new("bh", Reg(3), Bitness::I8, 7, RegClass::General)
+        },
+    ],
+};
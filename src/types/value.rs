@@ -3,7 +3,15 @@
 //! This module provides types for representing values with explicit bit-width
 //! information, constants, and load/store operations.
 
+use super::ops::{Endian, Ext, Sign};
 use super::*;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex};
+use core::ops::{
+    Add, AddAssign, BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Div, DivAssign,
+    Mul, MulAssign, Neg, Not, Rem, RemAssign, Shl, ShlAssign, Shr, ShrAssign, Sub, SubAssign,
+};
+use core::str::FromStr;
 
 /// The bit width of a value, represented logarithmically.
 ///
@@ -20,6 +28,10 @@ use super::*;
 /// let byte = Bitness { log2: 3 };  // 2^3 = 8 bits
 /// let word = Bitness { log2: 5 };  // 2^5 = 32 bits
 /// let qword = Bitness { log2: 6 }; // 2^6 = 64 bits
+///
+/// assert_eq!(qword, Bitness::I64);
+/// assert_eq!(qword.bits(), 64);
+/// assert_eq!(Bitness::from_bits(32), Some(word));
 /// ```
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[cfg_attr(feature = "enum-map", derive(enum_map::Enum))]
@@ -29,11 +41,161 @@ pub struct Bitness {
     /// The logarithm base 2 of the bit width
     pub log2: u8,
 }
+impl Bitness {
+    /// The largest valid [`Bitness::log2`], describing a 512-bit value.
+    pub const MAX_LOG2: u8 = 9;
+    /// A 1-bit value, such as a flag
+    pub const I1: Bitness = Bitness { log2: 0 };
+    /// An 8-bit value
+    pub const I8: Bitness = Bitness { log2: 3 };
+    /// A 16-bit value
+    pub const I16: Bitness = Bitness { log2: 4 };
+    /// A 32-bit value
+    pub const I32: Bitness = Bitness { log2: 5 };
+    /// A 64-bit value
+    pub const I64: Bitness = Bitness { log2: 6 };
+    /// A 128-bit value
+    pub const I128: Bitness = Bitness { log2: 7 };
+    /// A 256-bit value
+    pub const I256: Bitness = Bitness { log2: 8 };
+    /// A 512-bit value
+    pub const I512: Bitness = Bitness { log2: 9 };
+
+    /// Creates a bitness from its logarithm, or returns `None` if the width
+    /// would exceed 512 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(Bitness::new(9), Some(Bitness::I512));
+    /// assert_eq!(Bitness::new(10), None);
+    /// ```
+    pub const fn new(log2: u8) -> Option<Self> {
+        if log2 > Self::MAX_LOG2 {
+            None
+        } else {
+            Some(Bitness { log2 })
+        }
+    }
+
+    /// Creates a bitness from a width in bits, or returns `None` if `bits` is
+    /// not a power of two no greater than 512.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(Bitness::from_bits(16), Some(Bitness::I16));
+    /// assert_eq!(Bitness::from_bits(24), None);
+    /// assert_eq!(Bitness::from_bits(1024), None);
+    /// ```
+    pub const fn from_bits(bits: u32) -> Option<Self> {
+        if bits.is_power_of_two() {
+            Self::new(bits.trailing_zeros() as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the width does not exceed 512 bits.
+    pub const fn is_valid(self) -> bool {
+        self.log2 <= Self::MAX_LOG2
+    }
+
+    /// Returns the width in bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `log2` is 32 or more, since the width does not fit in a
+    /// `u32`. Such a bitness is also invalid; see [`Bitness::is_valid`].
+    pub const fn bits(self) -> u32 {
+        match 1u32.checked_shl(self.log2 as u32) {
+            Some(bits) => bits,
+            None => panic!("bitness too wide for a u32 bit count"),
+        }
+    }
+
+    /// Returns the number of bytes needed to hold a value of this width.
+    ///
+    /// Widths narrower than a byte round up to one byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `log2` is 32 or more, like [`Bitness::bits`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert_eq!(Bitness::I128.bytes(), 16);
+    /// assert_eq!(Bitness::I1.bytes(), 1);
+    /// ```
+    pub const fn bytes(self) -> u32 {
+        self.bits().div_ceil(8)
+    }
+
+    /// Returns a constant with the low [`Bitness::bits`] bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// assert_eq!(Bitness::I16.mask(), Constant::from(0xffffu16));
+    /// assert_eq!(Bitness::I512.mask(), Constant::MAX);
+    /// ```
+    pub fn mask(self) -> Constant {
+        Constant::MAX.masked(self)
+    }
+
+    /// Returns the bitness of a value twice as wide as this one.
+    ///
+    /// Returns `None` for 512-bit values, since the doubled width would not
+    /// fit in a [`Constant`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Bitness;
+    ///
+    /// assert!(Bitness { log2: 6 }.widened() == Some(Bitness { log2: 7 }));
+    /// assert!(Bitness { log2: 9 }.widened().is_none());
+    /// assert!(Bitness { log2: 255 }.widened().is_none());
+    /// ```
+    pub fn widened(self) -> Option<Bitness> {
+        self.log2.checked_add(1).and_then(Self::new)
+    }
+}
 /// A large constant value up to 512 bits.
 ///
-/// Stores a constant value as eight 64-bit words in native endianness
-/// (typically little-endian). The constant can be interpreted at various
-/// bit widths using the provided methods.
+/// Stores a constant value as eight 64-bit words, least significant word
+/// first. The constant can be interpreted at various bit widths using the
+/// provided methods, and serialized in either byte order with
+/// [`Constant::to_bytes`] and [`Constant::from_bytes_endian`].
+///
+/// `Constant` behaves as an unsigned 512-bit integer: the [`core::ops`]
+/// operators wrap modulo 2^512 and [`Ord`] compares numerically. Operations at
+/// a narrower [`Bitness`] are available through [`Arith::eval`], and signed
+/// interpretations through [`Constant::is_negative`] and
+/// [`Constant::to_i128`].
+///
+/// The numeric [`Ord`] replaces the derived ordering this type used to have,
+/// which compared `data` word by word starting with the least significant
+/// one. Sorted collections of constants and `BTreeMap` keys built with an
+/// older version now iterate in a different order.
+///
+/// ```
+/// use portal_pc_asm_common::types::value::Constant;
+///
+/// // The derived ordering put 2^64 below 2, since its low word is zero.
+/// assert!(Constant::ONE << 64 > Constant::from(2u64));
+/// ```
+///
+/// [`Arith::eval`]: super::ops::Arith::eval
 ///
 /// # Examples
 ///
@@ -50,17 +212,18 @@ pub struct Bitness {
 /// let bytes: Vec<u8> = constant.bytes(bitness).collect();
 /// # }
 /// ```
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Constant {
-    /// The constant data stored in native endianness (usually little-endian)
+    /// The constant data, least significant word first
     pub data: [u64; 8],
 }
 impl Constant {
     /// Returns an iterator over the bytes of this constant.
     ///
     /// The number of bytes returned is determined by the bitness parameter.
-    /// Bytes are in native endianness order.
+    /// Bytes are in the host's byte order; this is equivalent to
+    /// [`Constant::to_bytes`] with [`Endian::NATIVE`].
     ///
     /// # Examples
     ///
@@ -75,10 +238,45 @@ impl Constant {
     /// # }
     /// ```
     pub fn bytes(&self, b: Bitness) -> impl Iterator<Item = u8> {
-        self.data
-            .into_iter()
-            .flat_map(|a| a.to_ne_bytes())
-            .take(1 << (b.log2 - 3))
+        self.to_bytes(Endian::NATIVE, b)
+    }
+
+    /// Returns an iterator over the bytes of this constant in the given byte
+    /// order.
+    ///
+    /// The number of bytes returned is determined by the bitness parameter.
+    /// Widths narrower than a byte produce a single byte holding the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Endian;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let constant = Constant { data: [0x0102_0304, 0, 0, 0, 0, 0, 0, 0] };
+    /// let word = Bitness { log2: 5 };
+    ///
+    /// let mut le = [0; 4];
+    /// le.iter_mut().zip(constant.to_bytes(Endian::Little, word)).for_each(|(d, s)| *d = s);
+    /// assert_eq!(le, [4, 3, 2, 1]);
+    ///
+    /// let mut be = [0; 4];
+    /// be.iter_mut().zip(constant.to_bytes(Endian::Big, word)).for_each(|(d, s)| *d = s);
+    /// assert_eq!(be, [1, 2, 3, 4]);
+    /// ```
+    pub fn to_bytes(&self, endian: Endian, b: Bitness) -> impl Iterator<Item = u8> {
+        let n = b.bytes() as usize;
+        let le: [u8; 64] = array_init::from_iter(
+            self.masked(b)
+                .data
+                .into_iter()
+                .flat_map(|a| a.to_le_bytes()),
+        )
+        .unwrap();
+        (0..n).map(move |i| match endian {
+            Endian::Little => le[i],
+            Endian::Big => le[n - 1 - i],
+        })
     }
 
     /// Returns an iterator over the bits of this constant.
@@ -99,25 +297,66 @@ impl Constant {
     /// # }
     /// ```
     pub fn bits(&self, b: Bitness) -> impl Iterator<Item = bool> {
-        self.bytes(b)
+        self.to_bytes(Endian::Little, b)
             .flat_map(|a| bitvec::array::BitArray::<u8, Lsb0>::new(a).into_iter())
-            .take(1 << (b.log2))
+            .take(b.bits() as usize)
     }
 
     /// Creates a constant from an iterator of bytes.
     ///
     /// The bitness parameter determines how many bytes are consumed from the iterator.
+    /// Bytes are in the host's byte order; this is equivalent to
+    /// [`Constant::from_bytes_endian`] with [`Endian::NATIVE`].
     /// Remaining space is zero-filled.
     ///
     /// Returns `None` if the iterator doesn't provide enough bytes.
     pub fn from_bytes(b: Bitness, i: impl Iterator<Item = u8>) -> Option<Self> {
-        Some(Self {
-            data: array_init::from_iter(
-                i.chain(once(0u8).cycle().take((512 / 8) - (1 << (b.log2 - 3))))
-                    .batching(|i| array_init::from_iter(i))
-                    .map(|a| u64::from_ne_bytes(a)),
-            )?,
-        })
+        Self::from_bytes_endian(b, Endian::NATIVE, i)
+    }
+
+    /// Creates a constant from an iterator of bytes in the given byte order.
+    ///
+    /// The bitness parameter determines how many bytes are consumed from the
+    /// iterator. Remaining space is zero-filled, and bits above the width are
+    /// cleared.
+    ///
+    /// Returns `None` if the iterator doesn't provide enough bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Endian;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let word = Bitness { log2: 5 };
+    /// let c = Constant::from_bytes_endian(word, Endian::Big, [1, 2, 3, 4].into_iter()).unwrap();
+    /// assert_eq!(c.data[0], 0x0102_0304);
+    ///
+    /// assert!(Constant::from_bytes_endian(word, Endian::Big, [1, 2].into_iter()).is_none());
+    /// ```
+    pub fn from_bytes_endian(
+        b: Bitness,
+        endian: Endian,
+        i: impl Iterator<Item = u8>,
+    ) -> Option<Self> {
+        let n = b.bytes() as usize;
+        let mut le = [0u8; 64];
+        let mut len = 0;
+        for byte in i.take(n) {
+            le[len] = byte;
+            len += 1;
+        }
+        if len < n {
+            return None;
+        }
+        if endian == Endian::Big {
+            le[..n].reverse();
+        }
+        let data: [u64; 8] = array_init::from_iter(
+            le.chunks_exact(8)
+                .map(|a| u64::from_le_bytes(a.try_into().unwrap())),
+        )?;
+        Some(Self { data }.masked(b))
     }
 
     /// Creates a constant from an iterator of bits.
@@ -127,10 +366,11 @@ impl Constant {
     ///
     /// Returns `None` if the iterator doesn't provide enough bits.
     pub fn from_bits(b: Bitness, i: impl Iterator<Item = bool>) -> Option<Self> {
-        Self::from_bytes(
+        Self::from_bytes_endian(
             b,
+            Endian::Little,
             i.chain(once(false).cycle().take(
-                8 - (match (1 << (b.log2)) % 8 {
+                8 - (match b.bits() as usize % 8 {
                     0 => 8,
                     a => a,
                 }),
@@ -146,6 +386,499 @@ impl Constant {
         )
     }
 }
+impl Constant {
+    /// The constant with every bit clear.
+    pub const ZERO: Self = Self { data: [0; 8] };
+    /// The constant one.
+    pub const ONE: Self = Self {
+        data: [1, 0, 0, 0, 0, 0, 0, 0],
+    };
+    /// The constant with every bit set.
+    pub const MAX: Self = Self {
+        data: [u64::MAX; 8],
+    };
+
+    /// Returns bit `i` (LSB-first) of this constant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is 512 or greater.
+    pub fn bit(&self, i: u32) -> bool {
+        (self.data[(i / 64) as usize] >> (i % 64)) & 1 != 0
+    }
+
+    /// Returns `true` if every bit of this constant is clear.
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().all(|&w| w == 0)
+    }
+
+    /// Clears every bit at or above the width given by `b`.
+    pub(crate) fn masked(mut self, b: Bitness) -> Self {
+        let bits = b.bits();
+        for (i, w) in self.data.iter_mut().enumerate() {
+            let lo = i as u32 * 64;
+            if lo >= bits {
+                *w = 0;
+            } else if bits - lo < 64 {
+                *w &= (1u64 << (bits - lo)) - 1;
+            }
+        }
+        self
+    }
+
+    /// Interprets the low bits as a signed value of width `b` and copies its
+    /// sign bit into every higher bit.
+    pub(crate) fn sign_extended(self, b: Bitness) -> Self {
+        let bits = b.bits();
+        let low = self.masked(b);
+        if bits >= 512 || !low.bit(bits - 1) {
+            return low;
+        }
+        low.zip_limbs(Self::ZERO.inverted().masked(b).inverted(), |a, b| a | b)
+    }
+
+    /// Combines two constants limb by limb.
+    pub(crate) fn zip_limbs(self, rhs: Self, mut f: impl FnMut(u64, u64) -> u64) -> Self {
+        Self {
+            data: core::array::from_fn(|i| f(self.data[i], rhs.data[i])),
+        }
+    }
+
+    /// Flips every bit.
+    pub(crate) fn inverted(self) -> Self {
+        Self {
+            data: self.data.map(|w| !w),
+        }
+    }
+
+    /// Adds two constants modulo 2^512.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        let mut carry = false;
+        self.zip_limbs(rhs, |a, b| {
+            let (s, c1) = a.overflowing_add(b);
+            let (s, c2) = s.overflowing_add(carry as u64);
+            carry = c1 || c2;
+            s
+        })
+    }
+
+    /// Subtracts two constants modulo 2^512.
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        let mut borrow = false;
+        self.zip_limbs(rhs, |a, b| {
+            let (d, b1) = a.overflowing_sub(b);
+            let (d, b2) = d.overflowing_sub(borrow as u64);
+            borrow = b1 || b2;
+            d
+        })
+    }
+
+    /// Negates a constant modulo 2^512.
+    pub fn wrapping_neg(self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+
+    /// Multiplies two constants modulo 2^512.
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        let mut out = [0u64; 8];
+        for i in 0..8 {
+            let mut carry = 0u128;
+            for j in 0..8 - i {
+                let t = out[i + j] as u128 + (self.data[i] as u128) * (rhs.data[j] as u128) + carry;
+                out[i + j] = t as u64;
+                carry = t >> 64;
+            }
+        }
+        Self { data: out }
+    }
+
+    /// Shifts left by `n` bits, discarding bits shifted past bit 511.
+    pub(crate) fn shifted_left(self, n: u32) -> Self {
+        if n >= 512 {
+            return Self::ZERO;
+        }
+        let (limbs, bits) = ((n / 64) as usize, n % 64);
+        Self {
+            data: core::array::from_fn(|i| {
+                if i < limbs {
+                    return 0;
+                }
+                let hi = self.data[i - limbs] << bits;
+                match (bits, i.checked_sub(limbs + 1)) {
+                    (0, _) | (_, None) => hi,
+                    (_, Some(j)) => hi | (self.data[j] >> (64 - bits)),
+                }
+            }),
+        }
+    }
+
+    /// Shifts right by `n` bits, filling with zeroes.
+    pub(crate) fn shifted_right(self, n: u32) -> Self {
+        if n >= 512 {
+            return Self::ZERO;
+        }
+        let (limbs, bits) = ((n / 64) as usize, n % 64);
+        Self {
+            data: core::array::from_fn(|i| {
+                let Some(lo) = self.data.get(i + limbs) else {
+                    return 0;
+                };
+                let lo = lo >> bits;
+                match (bits, self.data.get(i + limbs + 1)) {
+                    (0, _) | (_, None) => lo,
+                    (_, Some(hi)) => lo | (hi << (64 - bits)),
+                }
+            }),
+        }
+    }
+
+    /// Divides two constants as unsigned 512-bit integers, returning the
+    /// quotient and remainder, or `None` if `rhs` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Constant;
+    ///
+    /// let (q, r) = Constant::from(100u64).div_rem(Constant::from(7u64)).unwrap();
+    /// assert!((q, r) == (Constant::from(14u64), Constant::from(2u64)));
+    /// assert!(Constant::ONE.div_rem(Constant::ZERO).is_none());
+    /// ```
+    pub fn div_rem(self, rhs: Self) -> Option<(Self, Self)> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let mut q = Self::ZERO;
+        let mut r = Self::ZERO;
+        for i in (0..512).rev() {
+            let carry = r.bit(511);
+            r = r.shifted_left(1);
+            r.data[0] |= self.bit(i) as u64;
+            if carry || r >= rhs {
+                r = r.wrapping_sub(rhs);
+                q.data[(i / 64) as usize] |= 1 << (i % 64);
+            }
+        }
+        Some((q, r))
+    }
+}
+impl Constant {
+    /// Multiplies two values of width `bitness` without discarding any bits
+    /// of the product.
+    ///
+    /// Each operand is interpreted according to its own [`Sign`], which covers
+    /// the mixed-sign case of RISC-V `MULHSU` as well as x86 `MUL`/`IMUL`.
+    /// Returns the `(low, high)` halves of the product, each truncated to
+    /// `bitness`. Returns `None` if the product would be wider than 512 bits;
+    /// see [`Bitness::widened`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Sign;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let byte = Bitness { log2: 3 };
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    ///
+    /// // 0xff * 0xff = 0xfe01
+    /// let (lo, hi) = c(0xff)
+    ///     .widening_mul(&c(0xff), byte, Sign::Unsigned, Sign::Unsigned)
+    ///     .unwrap();
+    /// assert!(lo == c(0x01) && hi == c(0xfe));
+    ///
+    /// // -1 * -1 = 1
+    /// let (lo, hi) = c(0xff)
+    ///     .widening_mul(&c(0xff), byte, Sign::Signed, Sign::Signed)
+    ///     .unwrap();
+    /// assert!(lo == c(0x01) && hi == c(0x00));
+    /// ```
+    pub fn widening_mul(
+        &self,
+        rhs: &Constant,
+        bitness: Bitness,
+        lhs_sign: Sign,
+        rhs_sign: Sign,
+    ) -> Option<(Constant, Constant)> {
+        bitness.widened()?;
+        let operand = |v: &Constant, sign| match sign {
+            Sign::Unsigned => v.masked(bitness),
+            Sign::Signed => v.sign_extended(bitness),
+        };
+        let product = operand(self, lhs_sign).wrapping_mul(operand(rhs, rhs_sign));
+        Some((
+            product.masked(bitness),
+            product.shifted_right(bitness.bits()).masked(bitness),
+        ))
+    }
+}
+impl Constant {
+    /// Resizes a value of width `from` to width `to`.
+    ///
+    /// Bits above `from` are ignored. When widening, the new high bits are
+    /// filled according to `ext`; when narrowing, the value is truncated and
+    /// `ext` has no effect. Bits of the result above `to` are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Ext;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    /// let (nibble, byte) = (Bitness { log2: 2 }, Bitness { log2: 3 });
+    ///
+    /// assert!(c(0xa).extend(nibble, byte, Ext::Sign) == c(0xfa));
+    /// assert!(c(0xa).extend(nibble, byte, Ext::Zero) == c(0x0a));
+    /// assert!(c(0x3a).extend(nibble, byte, Ext::Zero) == c(0x0a));
+    /// ```
+    pub fn extend(&self, from: Bitness, to: Bitness, ext: Ext) -> Constant {
+        match ext {
+            Ext::Sign => self.sign_extended(from),
+            Ext::Zero => self.masked(from),
+        }
+        .masked(to)
+    }
+
+    /// Truncates this constant to width `to`, clearing every higher bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let c = |v| Constant { data: [v, 0, 0, 0, 0, 0, 0, 0] };
+    /// assert!(c(0x1234).truncate(Bitness { log2: 3 }) == c(0x34));
+    /// assert!(c(0b110).truncate(Bitness { log2: 1 }) == c(0b10));
+    /// ```
+    pub fn truncate(&self, to: Bitness) -> Constant {
+        self.masked(to)
+    }
+}
+impl Constant {
+    /// Returns the value as a `u64`, or `None` if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Constant;
+    ///
+    /// assert_eq!(Constant::from(42u8).to_u64(), Some(42));
+    /// assert_eq!((Constant::ONE << 64).to_u64(), None);
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        self.data[1..]
+            .iter()
+            .all(|&w| w == 0)
+            .then_some(self.data[0])
+    }
+
+    /// Returns the value as a `u128`, or `None` if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::Constant;
+    ///
+    /// assert_eq!((Constant::ONE << 64).to_u128(), Some(1 << 64));
+    /// assert_eq!((Constant::ONE << 128).to_u128(), None);
+    /// ```
+    pub fn to_u128(&self) -> Option<u128> {
+        self.data[2..]
+            .iter()
+            .all(|&w| w == 0)
+            .then_some(self.data[0] as u128 | (self.data[1] as u128) << 64)
+    }
+
+    /// Returns `true` if the sign bit of a value of width `b` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// assert!(Constant::from(0x80u64).is_negative(Bitness { log2: 3 }));
+    /// assert!(!Constant::from(0x80u64).is_negative(Bitness { log2: 4 }));
+    /// ```
+    pub fn is_negative(&self, b: Bitness) -> bool {
+        self.bit(b.bits() - 1)
+    }
+
+    /// Interprets the low bits as a signed value of width `b` and returns it
+    /// as an `i64`, or `None` if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// assert_eq!(Constant::from(0xfffeu64).to_i64(Bitness { log2: 4 }), Some(-2));
+    /// assert_eq!(Constant::from(0xfffeu64).to_i64(Bitness { log2: 5 }), Some(0xfffe));
+    /// ```
+    pub fn to_i64(&self, b: Bitness) -> Option<i64> {
+        i64::try_from(self.to_i128(b)?).ok()
+    }
+
+    /// Interprets the low bits as a signed value of width `b` and returns it
+    /// as an `i128`, or `None` if it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant};
+    ///
+    /// let zmm = Bitness { log2: 9 };
+    /// assert_eq!(Constant::MAX.to_i128(zmm), Some(-1));
+    /// assert_eq!((Constant::ONE << 200).to_i128(zmm), None);
+    /// assert_eq!(Constant::from(-5i128).to_i128(zmm), Some(-5));
+    /// ```
+    pub fn to_i128(&self, b: Bitness) -> Option<i128> {
+        let v = self.sign_extended(b);
+        let fill = if v.bit(511) { u64::MAX } else { 0 };
+        let low = v.data[0] as u128 | (v.data[1] as u128) << 64;
+        (v.data[2..].iter().all(|&w| w == fill) && v.bit(127) == v.bit(511)).then_some(low as i128)
+    }
+}
+impl PartialOrd for Constant {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Constant {
+    /// Compares the constants as unsigned 512-bit integers.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.iter().rev().cmp(other.data.iter().rev())
+    }
+}
+impl From<u8> for Constant {
+    fn from(value: u8) -> Self {
+        Self::from(u64::from(value))
+    }
+}
+impl From<u16> for Constant {
+    fn from(value: u16) -> Self {
+        Self::from(u64::from(value))
+    }
+}
+impl From<u32> for Constant {
+    fn from(value: u32) -> Self {
+        Self::from(u64::from(value))
+    }
+}
+impl From<u64> for Constant {
+    fn from(value: u64) -> Self {
+        Self {
+            data: [value, 0, 0, 0, 0, 0, 0, 0],
+        }
+    }
+}
+impl From<u128> for Constant {
+    fn from(value: u128) -> Self {
+        Self {
+            data: [value as u64, (value >> 64) as u64, 0, 0, 0, 0, 0, 0],
+        }
+    }
+}
+impl From<i8> for Constant {
+    /// Sign-extends the value to 512 bits.
+    fn from(value: i8) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+impl From<i16> for Constant {
+    /// Sign-extends the value to 512 bits.
+    fn from(value: i16) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+impl From<i32> for Constant {
+    /// Sign-extends the value to 512 bits.
+    fn from(value: i32) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+impl From<i64> for Constant {
+    /// Sign-extends the value to 512 bits.
+    fn from(value: i64) -> Self {
+        Self::from(i128::from(value))
+    }
+}
+impl From<i128> for Constant {
+    /// Sign-extends the value to 512 bits.
+    fn from(value: i128) -> Self {
+        let fill = if value < 0 { u64::MAX } else { 0 };
+        let mut data = [fill; 8];
+        data[0] = value as u64;
+        data[1] = (value >> 64) as u64;
+        Self { data }
+    }
+}
+impl Not for Constant {
+    type Output = Self;
+    fn not(self) -> Self {
+        self.inverted()
+    }
+}
+impl Neg for Constant {
+    type Output = Self;
+    /// Negates modulo 2^512.
+    fn neg(self) -> Self {
+        self.wrapping_neg()
+    }
+}
+impl Shl<u32> for Constant {
+    type Output = Self;
+    /// Shifts left, yielding zero for shifts of 512 bits or more.
+    fn shl(self, rhs: u32) -> Self {
+        self.shifted_left(rhs)
+    }
+}
+impl Shr<u32> for Constant {
+    type Output = Self;
+    /// Shifts right logically, yielding zero for shifts of 512 bits or more.
+    fn shr(self, rhs: u32) -> Self {
+        self.shifted_right(rhs)
+    }
+}
+impl ShlAssign<u32> for Constant {
+    fn shl_assign(&mut self, rhs: u32) {
+        *self = *self << rhs;
+    }
+}
+impl ShrAssign<u32> for Constant {
+    fn shr_assign(&mut self, rhs: u32) {
+        *self = *self >> rhs;
+    }
+}
+/// Implements a binary operator and its assigning form for [`Constant`].
+macro_rules! constant_binop {
+    ($($tr:ident::$f:ident, $atr:ident::$af:ident => |$a:ident, $b:ident| $e:expr;)*) => {
+        $(
+            impl $tr for Constant {
+                type Output = Self;
+                fn $f(self, rhs: Self) -> Self {
+                    let ($a, $b) = (self, rhs);
+                    $e
+                }
+            }
+            impl $atr for Constant {
+                fn $af(&mut self, rhs: Self) {
+                    *self = $tr::$f(*self, rhs);
+                }
+            }
+        )*
+    };
+}
+constant_binop! {
+    Add::add, AddAssign::add_assign => |a, b| a.wrapping_add(b);
+    Sub::sub, SubAssign::sub_assign => |a, b| a.wrapping_sub(b);
+    Mul::mul, MulAssign::mul_assign => |a, b| a.wrapping_mul(b);
+    Div::div, DivAssign::div_assign => |a, b| a.div_rem(b).expect("attempt to divide by zero").0;
+    Rem::rem, RemAssign::rem_assign => |a, b| a.div_rem(b).expect("attempt to calculate the remainder with a divisor of zero").1;
+    BitAnd::bitand, BitAndAssign::bitand_assign => |a, b| a.zip_limbs(b, |a, b| a & b);
+    BitOr::bitor, BitOrAssign::bitor_assign => |a, b| a.zip_limbs(b, |a, b| a | b);
+    BitXor::bitxor, BitXorAssign::bitxor_assign => |a, b| a.zip_limbs(b, |a, b| a ^ b);
+}
 /// A value with an offset and bit width.
 ///
 /// Represents a value that has both an offset (of generic type `G`) and
@@ -167,7 +900,7 @@ impl Constant {
 ///     bitness: Bitness { log2: 6 }, // 64 bits
 /// };
 /// ```
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Value<G> {
     /// The offset or location of the value
@@ -204,6 +937,105 @@ impl<G> Value<G> {
         })
     }
 }
+/// A conversion of a value to a different bit width.
+///
+/// Describes `movsx`/`movzx` on x86, `sxtw`/`uxtb` on AArch64, `sext.w` on
+/// RISC-V and truncating moves uniformly. The source [`Value`] carries the
+/// width that is read, so `sext.w a0, a1` is an extension of the low 32 bits
+/// of `a1` to 64 bits.
+///
+/// # Type Parameters
+///
+/// - `G`: The type of offset of the source value
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::ops::Ext;
+/// use portal_pc_asm_common::types::reg::Reg;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant, Resize, Value};
+///
+/// // sext.w a0, a1
+/// let sext_w = Resize::new(
+///     Value { offset: Reg(11), bitness: Bitness { log2: 5 } },
+///     Bitness { log2: 6 },
+///     Ext::Sign,
+/// );
+/// assert!(matches!(sext_w, Resize::Extend { ext: Ext::Sign, .. }));
+///
+/// let c = Constant { data: [0x8000_0000, 0, 0, 0, 0, 0, 0, 0] };
+/// assert!(sext_w.apply(&c).data[0] == 0xffff_ffff_8000_0000);
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Resize<G> {
+    /// Widen the value, filling the new high bits according to `ext`
+    Extend {
+        /// The value being read
+        value: Value<G>,
+        /// The bit width of the result
+        to: Bitness,
+        /// How the new high bits are filled
+        ext: Ext,
+    },
+    /// Narrow the value, discarding its high bits
+    Truncate {
+        /// The value being read
+        value: Value<G>,
+        /// The bit width of the result
+        to: Bitness,
+    },
+}
+impl<G> Resize<G> {
+    /// Creates a conversion of `value` to width `to`.
+    ///
+    /// Returns [`Resize::Truncate`] if `to` is narrower than the value, and
+    /// [`Resize::Extend`] using `ext` otherwise.
+    pub fn new(value: Value<G>, to: Bitness, ext: Ext) -> Self {
+        if to < value.bitness {
+            Resize::Truncate { value, to }
+        } else {
+            Resize::Extend { value, to, ext }
+        }
+    }
+
+    /// Returns the value being read.
+    pub fn value(&self) -> &Value<G> {
+        match self {
+            Resize::Extend { value, .. } | Resize::Truncate { value, .. } => value,
+        }
+    }
+
+    /// Returns the bit width of the result.
+    pub fn to(&self) -> Bitness {
+        match self {
+            Resize::Extend { to, .. } | Resize::Truncate { to, .. } => *to,
+        }
+    }
+
+    /// Applies this conversion to a constant holding the source value.
+    pub fn apply(&self, c: &Constant) -> Constant {
+        match self {
+            Resize::Extend { value, to, ext } => c.extend(value.bitness, *to, *ext),
+            Resize::Truncate { to, .. } => c.truncate(*to),
+        }
+    }
+
+    /// Maps the offset of the source value using a fallible function.
+    pub fn map<G2, E>(self, f: &mut (dyn FnMut(G) -> Result<G2, E> + '_)) -> Result<Resize<G2>, E> {
+        Ok(match self {
+            Resize::Extend { value, to, ext } => Resize::Extend {
+                value: value.map(f)?,
+                to,
+                ext,
+            },
+            Resize::Truncate { value, to } => Resize::Truncate {
+                value: value.map(f)?,
+                to,
+            },
+        })
+    }
+}
 /// A frame for load/store operations, either from a value or a constant.
 ///
 /// Represents either a value loaded from a location (like a register or memory)
@@ -231,7 +1063,7 @@ impl<G> Value<G> {
 ///     constant: Constant { data: [42, 0, 0, 0, 0, 0, 0, 0] },
 /// };
 /// ```
-#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LoadStoreFrame<G> {
     /// Load/store from a value at a specific bit offset
@@ -313,6 +1145,234 @@ impl<G> LoadStoreFrame<G> {
         }
     }
 }
+impl<G> LoadStoreFrame<G> {
+    /// Returns the bytes of a constant frame in the given byte order.
+    ///
+    /// Returns `None` for value frames, whose contents are not known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use portal_pc_asm_common::types::ops::Endian;
+    /// use portal_pc_asm_common::types::reg::Reg;
+    /// use portal_pc_asm_common::types::value::{Bitness, Constant, LoadStoreFrame};
+    ///
+    /// let frame: LoadStoreFrame<Reg> = LoadStoreFrame::Constant {
+    ///     bits: Bitness { log2: 4 },
+    ///     constant: Constant { data: [0x1234, 0, 0, 0, 0, 0, 0, 0] },
+    /// };
+    /// let mut bytes = frame.to_bytes(Endian::Big).unwrap();
+    /// assert_eq!((bytes.next(), bytes.next(), bytes.next()), (Some(0x12), Some(0x34), None));
+    /// ```
+    pub fn to_bytes(&self, endian: Endian) -> Option<impl Iterator<Item = u8>> {
+        match self {
+            LoadStoreFrame::Value { .. } => None,
+            LoadStoreFrame::Constant { bits, constant } => Some(constant.to_bytes(endian, *bits)),
+        }
+    }
+
+    /// Creates a constant frame of width `bits` from bytes in the given byte
+    /// order.
+    ///
+    /// Returns `None` if the iterator doesn't provide enough bytes.
+    pub fn from_bytes(bits: Bitness, endian: Endian, i: impl Iterator<Item = u8>) -> Option<Self> {
+        Some(LoadStoreFrame::Constant {
+            bits,
+            constant: Constant::from_bytes_endian(bits, endian, i)?,
+        })
+    }
+}
+/// An error returned when parsing the textual form of a value type fails.
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::value::{Bitness, ParseError};
+///
+/// assert_eq!("i24".parse::<Bitness>(), Err(ParseError::Bitness));
+/// ```
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Copy, Hash, Debug)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The bitness was not `i` followed by a power of two up to 512
+    Bitness,
+    /// The constant was not a decimal or `0x`-prefixed hexadecimal number
+    /// that fits in 512 bits
+    Constant,
+    /// The offset of a value could not be parsed
+    Offset,
+    /// The input did not have the expected overall shape
+    Syntax,
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseError::Bitness => "invalid bitness",
+            ParseError::Constant => "invalid constant",
+            ParseError::Offset => "invalid offset",
+            ParseError::Syntax => "invalid syntax",
+        })
+    }
+}
+impl core::error::Error for ParseError {}
+/// Formats as `i` followed by the bit width, such as `i32`.
+impl Display for Bitness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match 1u32.checked_shl(self.log2.into()) {
+            Some(bits) => write!(f, "i{bits}"),
+            None => write!(f, "i2^{}", self.log2),
+        }
+    }
+}
+impl Debug for Bitness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+impl FromStr for Bitness {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('i')
+            .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|d| d.parse().ok())
+            .and_then(Bitness::from_bits)
+            .ok_or(ParseError::Bitness)
+    }
+}
+impl Constant {
+    /// Writes the hexadecimal digits of this constant, without leading zeros.
+    fn fmt_hex(&self, f: &mut Formatter<'_>, digits: &[u8; 16]) -> fmt::Result {
+        let mut buf = [0u8; 128];
+        let mut len = 0;
+        for i in (0..128).rev() {
+            let d = (self.data[i / 16] >> ((i % 16) * 4)) & 0xf;
+            if d != 0 || len != 0 || i == 0 {
+                buf[len] = digits[d as usize];
+                len += 1;
+            }
+        }
+        // The buffer only ever holds ASCII hex digits.
+        f.pad_integral(true, "0x", core::str::from_utf8(&buf[..len]).unwrap())
+    }
+}
+impl LowerHex for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, b"0123456789abcdef")
+    }
+}
+impl UpperHex for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_hex(f, b"0123456789ABCDEF")
+    }
+}
+/// Formats as `0x`-prefixed lowercase hexadecimal, such as `0x1234`.
+impl Display for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:#x}")
+    }
+}
+impl Debug for Constant {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Constant({self:#x})")
+    }
+}
+/// Parses a decimal or `0x`-prefixed hexadecimal number.
+impl FromStr for Constant {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (digits, radix) = match s.strip_prefix("0x") {
+            Some(hex) => (hex, 16u32),
+            None => (s, 10u32),
+        };
+        if digits.is_empty() {
+            return Err(ParseError::Constant);
+        }
+        let radix_c = Constant::from(radix);
+        let (limit, limit_digit) = Constant::MAX.div_rem(radix_c).unwrap();
+        let mut acc = Constant::ZERO;
+        for c in digits.chars() {
+            let d = Constant::from(c.to_digit(radix).ok_or(ParseError::Constant)?);
+            if acc > limit || (acc == limit && d > limit_digit) {
+                return Err(ParseError::Constant);
+            }
+            acc = acc * radix_c + d;
+        }
+        Ok(acc)
+    }
+}
+/// Formats as the offset followed by `.` and the bitness, such as `r5.i64`.
+impl<G: Display> Display for Value<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.offset, self.bitness)
+    }
+}
+impl<G: FromStr> FromStr for Value<G> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (offset, bitness) = s.rsplit_once('.').ok_or(ParseError::Syntax)?;
+        Ok(Value {
+            offset: offset.parse().map_err(|_| ParseError::Offset)?,
+            bitness: bitness.parse()?,
+        })
+    }
+}
+/// Formats constant frames as `0x1234:i32` and value frames as the value
+/// followed by the bit offset and accessed width, such as `r5.i64[8:i16]`.
+///
+/// The output can be parsed back with [`FromStr`].
+///
+/// # Examples
+///
+/// ```
+/// use portal_pc_asm_common::types::reg::Reg;
+/// use portal_pc_asm_common::types::value::{Bitness, Constant, LoadStoreFrame, Value};
+///
+/// let frame: LoadStoreFrame<Reg> = LoadStoreFrame::Value {
+///     bits: Bitness { log2: 4 },
+///     val: Value { offset: Reg(5), bitness: Bitness { log2: 6 } },
+///     bit_offset: 8,
+/// };
+/// assert_eq!(frame.to_string(), "r5.i64[8:i16]");
+/// assert_eq!("r5.i64[8:i16]".parse(), Ok(frame));
+///
+/// let frame: LoadStoreFrame<Reg> = "0x1234:i32".parse().unwrap();
+/// assert_eq!(
+///     frame,
+///     LoadStoreFrame::Constant { bits: Bitness { log2: 5 }, constant: Constant::from(0x1234u32) },
+/// );
+/// assert_eq!(frame.to_string(), "0x1234:i32");
+/// ```
+impl<G: Display> Display for LoadStoreFrame<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadStoreFrame::Value {
+                bits,
+                val,
+                bit_offset,
+            } => write!(f, "{val}[{bit_offset}:{bits}]"),
+            LoadStoreFrame::Constant { bits, constant } => write!(f, "{constant}:{bits}"),
+        }
+    }
+}
+impl<G: FromStr> FromStr for LoadStoreFrame<G> {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_suffix(']') {
+            let (val, access) = rest.rsplit_once('[').ok_or(ParseError::Syntax)?;
+            let (bit_offset, bits) = access.split_once(':').ok_or(ParseError::Syntax)?;
+            return Ok(LoadStoreFrame::Value {
+                bits: bits.parse()?,
+                val: val.parse()?,
+                bit_offset: bit_offset.parse().map_err(|_| ParseError::Syntax)?,
+            });
+        }
+        let (constant, bits) = s.rsplit_once(':').ok_or(ParseError::Syntax)?;
+        Ok(LoadStoreFrame::Constant {
+            bits: bits.parse()?,
+            constant: constant.parse()?,
+        })
+    }
+}
 /// Marker trait for iterators representing "any" semantics.
 ///
 /// This trait is automatically implemented for all iterator types.
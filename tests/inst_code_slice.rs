@@ -0,0 +1,120 @@
+use core::ops::Range;
+
+use portal_pc_asm_common::types::code::InstCodeSlice;
+use portal_pc_asm_common::types::ops::Endian;
+use portal_pc_asm_common::types::value::{Bitness, Constant};
+use proptest::prelude::*;
+
+/// An instruction of up to 16 bytes, a byte order, and a list of disjoint,
+/// in-bounds bit ranges in arbitrary order.
+fn inst_and_field() -> impl Strategy<Value = (Vec<u8>, Endian, Vec<Range<usize>>)> {
+    (
+        prop::collection::vec(any::<u8>(), 1..=16),
+        prop_oneof![Just(Endian::Little), Just(Endian::Big)],
+    )
+        .prop_flat_map(|(bytes, endian)| {
+            let bits = bytes.len() * 8;
+            (
+                Just(bytes),
+                Just(endian),
+                prop::collection::btree_set(0..=bits, 0..8),
+            )
+        })
+        .prop_flat_map(|(bytes, endian, cuts)| {
+            let cuts: Vec<usize> = cuts.into_iter().collect();
+            let ranges: Vec<Range<usize>> = cuts.windows(2).map(|w| w[0]..w[1]).collect();
+            let len = ranges.len();
+            (
+                Just(bytes),
+                Just(endian),
+                Just(ranges).prop_shuffle(),
+                prop::collection::vec(any::<bool>(), len),
+            )
+        })
+        .prop_map(|(bytes, endian, ranges, keep)| {
+            let ranges = ranges.into_iter().zip(keep).filter(|(_, k)| *k);
+            (bytes, endian, ranges.map(|(r, _)| r).collect())
+        })
+}
+
+fn width(ranges: &[Range<usize>]) -> usize {
+    ranges.iter().map(|r| r.len()).sum()
+}
+
+fn constant(bytes: &[u8; 64]) -> Constant {
+    Constant::from_bytes_endian(Bitness::I512, Endian::Little, bytes.iter().copied()).unwrap()
+}
+
+fn low_bits(c: Constant, n: usize) -> Constant {
+    let mut out = Constant::ZERO;
+    for i in 0..n {
+        if c.bit(i as u32) {
+            out.data[i / 64] |= 1 << (i % 64);
+        }
+    }
+    out
+}
+
+/// The index of the byte holding bit `i`, computed independently of the
+/// crate.
+fn model_byte(len: usize, endian: Endian, i: usize) -> usize {
+    match endian {
+        Endian::Big => len - 1 - i / 8,
+        _ => i / 8,
+    }
+}
+
+proptest! {
+    #[test]
+    fn extract_after_with_returns_value(
+        (bytes, endian, ranges) in inst_and_field(),
+        val in prop::array::uniform32(any::<u8>()),
+    ) {
+        let mut wide = [0u8; 64];
+        wide[..32].copy_from_slice(&val);
+        let val = constant(&wide);
+        let inst = InstCodeSlice(bytes).with(endian, ranges.iter().cloned(), val);
+        let got = inst.extract(endian, ranges.iter().cloned());
+        prop_assert_eq!(got, low_bits(val, width(&ranges)));
+    }
+
+    #[test]
+    fn with_after_extract_is_identity((bytes, endian, ranges) in inst_and_field()) {
+        let inst = InstCodeSlice(bytes.clone());
+        let val = inst.extract(endian, ranges.iter().cloned());
+        prop_assert_eq!(inst.with(endian, ranges.iter().cloned(), val).0, bytes);
+    }
+
+    #[test]
+    fn with_only_touches_field_bits(
+        (bytes, endian, ranges) in inst_and_field(),
+        val in any::<u128>(),
+    ) {
+        let inst = InstCodeSlice(bytes.clone()).with(endian, ranges.iter().cloned(), val);
+        for i in 0..bytes.len() * 8 {
+            if ranges.iter().any(|r| r.contains(&i)) {
+                continue;
+            }
+            let byte = model_byte(bytes.len(), endian, i);
+            prop_assert_eq!((inst.0[byte] >> (i % 8)) & 1, (bytes[byte] >> (i % 8)) & 1);
+        }
+    }
+
+    #[test]
+    fn extract_matches_integer_model(bytes in prop::collection::vec(any::<u8>(), 1..=16)) {
+        let mut le = bytes.clone();
+        let mut be = [0u8; 16];
+        be[16 - bytes.len()..].copy_from_slice(&bytes);
+        le.resize(16, 0);
+        let inst = InstCodeSlice(&bytes[..]);
+        let all = 0..bytes.len() * 8;
+        prop_assert_eq!(
+            inst.extract(Endian::Little, [all.clone()].into_iter()).to_u128(),
+            Some(u128::from_le_bytes(le.try_into().unwrap()))
+        );
+        prop_assert_eq!(
+            inst.extract(Endian::Big, [all].into_iter()).to_u128(),
+            Some(u128::from_be_bytes(be))
+        );
+    }
+}